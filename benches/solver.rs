@@ -0,0 +1,33 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use salinity_rs::{Assumptions, compute_summaries, compute_summary, standard_seawater};
+use std::hint::black_box;
+
+const BATCH_SIZE: usize = 10_000;
+
+fn single_sample(c: &mut Criterion) {
+    let ass = Assumptions::default();
+    let inputs = standard_seawater(35.0, &ass);
+    c.bench_function("compute_summary", |b| {
+        b.iter(|| compute_summary(black_box(&inputs), black_box(&ass)))
+    });
+}
+
+fn batch(c: &mut Criterion) {
+    let ass = Assumptions::default();
+    // SP 30..40 so the solver does not see one sample 10k times.
+    let samples: Vec<_> = (0..BATCH_SIZE)
+        .map(|i| {
+            let sp = 30.0 + 10.0 * i as f64 / BATCH_SIZE as f64;
+            (standard_seawater(sp, &ass), ass.clone())
+        })
+        .collect();
+    let mut group = c.benchmark_group("compute_summaries");
+    group.sample_size(10);
+    group.bench_function("10k", |b| {
+        b.iter(|| compute_summaries(black_box(&samples), None))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, single_sample, batch);
+criterion_main!(benches);
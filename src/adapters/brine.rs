@@ -0,0 +1,29 @@
+//! High-salinity density correlation for brines beyond the TEOS-10 range.
+//!
+//! Sharqawy, Lienhard & Zubair (2010), Desalination and Water Treatment 16,
+//! eq. (8): a fit of seawater density at atmospheric pressure valid for
+//! 0–180 °C and 0–160 g/kg with ±0.1 % accuracy. It is used for
+//! `EosKind::BrineExtended`, where the TEOS-10 polynomial (validated to
+//! SA ≈ 42 g/kg) extrapolates poorly and even turns non-monotonic.
+
+/// Upper salinity bound (g/kg) of the correlation.
+pub const BRINE_SA_MAX: f64 = 160.0;
+/// Temperature bounds (°C) of the correlation.
+pub const BRINE_T_MIN_C: f64 = 0.0;
+pub const BRINE_T_MAX_C: f64 = 180.0;
+
+const A: [f64; 5] = [9.999e2, 2.034e-2, -6.162e-3, 2.261e-5, -4.657e-8];
+const B: [f64; 5] = [8.020e2, -2.001, 1.677e-2, -3.060e-5, -1.613e-5];
+
+/// Brine density (kg/m³) at atmospheric pressure from salinity (g/kg) and
+/// temperature (°C). Returns `NaN` outside the validity range.
+pub fn rho_brine(sa_g_kg: f64, t_c: f64) -> f64 {
+    if !(0.0..=BRINE_SA_MAX).contains(&sa_g_kg) || !(BRINE_T_MIN_C..=BRINE_T_MAX_C).contains(&t_c) {
+        return f64::NAN;
+    }
+    let w = sa_g_kg / 1000.0;
+    let t = t_c;
+    let rho_w = A[0] + t * (A[1] + t * (A[2] + t * (A[3] + t * A[4])));
+    let d_rho = w * (B[0] + t * (B[1] + t * (B[2] + t * B[3]))) + B[4] * w * w * t * t;
+    rho_w + d_rho
+}
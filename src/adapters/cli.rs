@@ -1,16 +1,33 @@
 use clap::Parser;
+#[cfg(feature = "rand")]
+use clap::{Args as ClapArgs, Subcommand};
+use serde::Serialize;
+use serde::de::{self, DeserializeOwned};
+use serde_json::Value;
 use std::fs;
 use std::io::{self, Read};
 
 use crate::error::AppError;
 use crate::models::{Assumptions, Inputs};
-use crate::salinity::calculator::CalculationSummary;
+use crate::salinity::calculator::{CalculationSummary, Components, compute_summaries};
+use crate::salinity::stats::batch_statistics;
+use crate::warning::Warning;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Salinity calculator (TEOS-10) — optional JSON output", long_about = None)]
 pub struct Args {
+    #[cfg(feature = "rand")]
+    #[command(subcommand)]
+    pub command: Option<Command>,
     #[arg(long)]
     json: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Human,
+        help = "Output format (--json is shorthand for --format json)"
+    )]
+    format: OutputFormat,
     #[arg(
         long,
         value_name = "FILE",
@@ -29,17 +46,155 @@ pub struct Args {
         help = "Inline JSON for assumptions (optional, supplements --inputs-json)"
     )]
     assumptions_json: Option<String>,
+    #[arg(
+        long,
+        help = "Reject unknown keys in input JSON instead of ignoring them"
+    )]
+    strict_json: bool,
+    #[arg(
+        long,
+        requires = "input",
+        help = "Treat --input as NDJSON (one document per line) and emit one JSON result per line"
+    )]
+    pub batch: bool,
+    #[arg(
+        long,
+        requires = "batch",
+        help = "Print batch progress as a percentage to stderr"
+    )]
+    progress: bool,
+    #[arg(
+        long,
+        requires = "batch",
+        help = "After the batch results, print one {\"stats\": ...} line with mean/min/max/stddev of SP, SA and density"
+    )]
+    stats: bool,
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "FIELDS",
+        help = "Only emit these summary fields in JSON and batch output (comma-separated, e.g. sp,sa)"
+    )]
+    fields: Vec<String>,
+    #[arg(
+        long,
+        help = "Wrap each summary value in JSON and batch output as {\"value\": ..., \"unit\": ...}"
+    )]
+    with_units: bool,
+    #[cfg(feature = "sqlite")]
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["KIND", "FILE"],
+        requires = "batch",
+        help = "Write batch results to a database instead of stdout (KIND: sqlite)"
+    )]
+    output: Vec<String>,
+    #[cfg(feature = "color")]
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ColorChoice::Auto,
+        help = "Highlight warnings in human output (auto honors NO_COLOR and TTY detection)"
+    )]
+    color: ColorChoice,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+    /// Fixed-width record, see `woce_record`.
+    Woce,
+    /// GitHub-flavored Markdown tables, see `markdown_tables`.
+    Markdown,
+}
+
+#[cfg(feature = "color")]
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[cfg(feature = "rand")]
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Emit synthetic standard-ratio seawater input documents (one JSON object per line)
+    Generate(GenerateArgs),
+}
+
+#[cfg(feature = "rand")]
+#[derive(ClapArgs, Debug)]
+pub struct GenerateArgs {
+    #[arg(long, default_value_t = 10, help = "Number of documents to generate")]
+    count: usize,
+    #[arg(
+        long,
+        default_value_t = 0,
+        help = "Seed for the random number generator"
+    )]
+    seed: u64,
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["MIN", "MAX"],
+        default_values_t = [30.0, 40.0],
+        allow_negative_numbers = true,
+        help = "Range of practical salinity to sample uniformly from"
+    )]
+    sp_range: Vec<f64>,
+}
+
+/// Parse `json` as `T`; with `strict`, first reject object keys that `T` does
+/// not define (typos such as `"sdoium"` are otherwise ignored by serde).
+fn from_json<T: DeserializeOwned + Serialize + Default>(
+    json: &str,
+    strict: bool,
+) -> Result<T, serde_json::Error> {
+    if !strict {
+        return serde_json::from_str(json);
+    }
+    let value: Value = serde_json::from_str(json)?;
+    check_known_fields::<T>(&value)?;
+    serde_json::from_value(value)
+}
+
+/// Error on keys of `value` not present in the serialized form of
+/// `T::default()`. Non-object values are left to the regular deserializer.
+fn check_known_fields<T: Serialize + Default>(value: &Value) -> Result<(), serde_json::Error> {
+    let known = serde_json::to_value(T::default())?;
+    let known: Vec<&str> = known
+        .as_object()
+        .map(|o| o.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    reject_unknown_keys(value, &known)
+}
+
+fn reject_unknown_keys(value: &Value, known: &[&str]) -> Result<(), serde_json::Error> {
+    if let Some(key) = value
+        .as_object()
+        .and_then(|o| o.keys().find(|k| !known.contains(&k.as_str())))
+    {
+        return Err(de::Error::custom(format_args!(
+            "unknown field `{key}`, expected one of {}",
+            known.join(", ")
+        )));
+    }
+    Ok(())
 }
 
 fn parse_inline_inputs(
     inputs_json: &str,
     assumptions_json: Option<&String>,
+    strict: bool,
 ) -> Result<(Inputs, Assumptions), AppError> {
     let inputs: Inputs =
-        serde_json::from_str(inputs_json).map_err(|source| AppError::ParseInputsJson { source })?;
+        from_json(inputs_json, strict).map_err(|source| AppError::ParseInputsJson { source })?;
 
     let assumptions = match assumptions_json {
-        Some(s) => serde_json::from_str::<Assumptions>(s)
+        Some(s) => from_json::<Assumptions>(s, strict)
             .map_err(|source| AppError::ParseAssumptionsJson { source })?,
         None => Assumptions::default(),
     };
@@ -47,69 +202,576 @@ fn parse_inline_inputs(
     Ok((inputs, assumptions))
 }
 
-fn parse_cmd_input_doc(doc: &str) -> Result<(Inputs, Assumptions), AppError> {
-    let parsed: CmdInput =
-        serde_json::from_str(doc).map_err(|source| AppError::ParseCmdInputJson { source })?;
+fn parse_cmd_input_doc(doc: &str, strict: bool) -> Result<(Inputs, Assumptions), AppError> {
+    let parsed = parse_cmd_input(doc, strict)?;
     Ok((parsed.inputs, parsed.assumptions.unwrap_or_default()))
 }
 
+fn parse_cmd_input(doc: &str, strict: bool) -> Result<CmdInput, AppError> {
+    let parse = || -> Result<CmdInput, serde_json::Error> {
+        if !strict {
+            return serde_json::from_str(doc);
+        }
+        let value: Value = serde_json::from_str(doc)?;
+        reject_unknown_keys(&value, &["inputs", "assumptions", "id", "timestamp"])?;
+        if let Some(inputs) = value.get("inputs") {
+            check_known_fields::<Inputs>(inputs)?;
+        }
+        if let Some(assumptions) = value.get("assumptions") {
+            check_known_fields::<Assumptions>(assumptions)?;
+        }
+        serde_json::from_value(value)
+    };
+    parse().map_err(|source| AppError::ParseCmdInputJson { source })
+}
+
+/// Read the `--input` source: a file path, or stdin for `-`.
+fn read_input_source(path: &str) -> Result<String, AppError> {
+    if path == "-" {
+        let mut s = String::new();
+        io::stdin()
+            .read_to_string(&mut s)
+            .map_err(|source| AppError::ReadStdin { source })?;
+        Ok(s)
+    } else {
+        fs::read_to_string(path).map_err(|source| AppError::ReadFile {
+            path: path.to_owned(),
+            source,
+        })
+    }
+}
+
 pub fn parse_inputs(args: &Args) -> Result<(Inputs, Assumptions), AppError> {
+    let strict = args.strict_json;
     match (&args.inputs_json, &args.input) {
-        (Some(inputs_json), _) => parse_inline_inputs(inputs_json, args.assumptions_json.as_ref()),
-        (None, Some(path)) if path == "-" => {
-            let mut s = String::new();
-            io::stdin()
-                .read_to_string(&mut s)
-                .map_err(|source| AppError::ReadStdin { source })?;
-            parse_cmd_input_doc(&s)
-        }
-        (None, Some(path)) => {
-            let s = fs::read_to_string(path).map_err(|source| AppError::ReadFile {
-                path: path.clone(),
-                source,
-            })?;
-            parse_cmd_input_doc(&s)
+        (Some(inputs_json), _) => {
+            parse_inline_inputs(inputs_json, args.assumptions_json.as_ref(), strict)
         }
+        (None, Some(path)) => parse_cmd_input_doc(&read_input_source(path)?, strict),
         (None, None) => Err(AppError::MissingInputData),
     }
 }
 
+/// One result line in `--batch` mode: the caller's identifiers followed by
+/// the summary fields.
+#[derive(Serialize)]
+struct BatchRecord<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<&'a str>,
+    #[serde(flatten)]
+    summary: Value,
+}
+
+/// Evaluate every non-blank line of the `--input` NDJSON stream and return
+/// one compact JSON result per line, echoing `id` and `timestamp`. With
+/// `--stats` a final `{"stats": ...}` line carries `batch_statistics`.
+pub fn run_batch(args: &Args) -> Result<Vec<String>, AppError> {
+    let results = evaluate_batch(args)?;
+    let mut lines = results
+        .iter()
+        .map(|(doc, summary)| {
+            let record = BatchRecord {
+                id: doc.id.as_deref(),
+                timestamp: doc.timestamp.as_deref(),
+                summary: summary_json(summary, &args.fields, args.with_units)?,
+            };
+            serde_json::to_string(&record).map_err(|source| AppError::SerializeOutput { source })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if args.stats {
+        let summaries: Vec<CalculationOutput> =
+            results.into_iter().map(|(_, summary)| summary).collect();
+        let stats = serde_json::json!({ "stats": batch_statistics(&summaries) });
+        lines.push(stats.to_string());
+    }
+    Ok(lines)
+}
+
+/// Parse and compute the `--batch` documents, pairing each with its
+/// (finite) summary.
+fn evaluate_batch(args: &Args) -> Result<Vec<(CmdInput, CalculationOutput)>, AppError> {
+    let path = args.input.as_deref().ok_or(AppError::MissingInputData)?;
+    let text = read_input_source(path)?;
+    let at_line = |idx: usize| {
+        move |source| AppError::BatchLine {
+            line: idx + 1,
+            source: Box::new(source),
+        }
+    };
+    let docs = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            parse_cmd_input(line, args.strict_json)
+                .map(|doc| (idx, doc))
+                .map_err(at_line(idx))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let samples: Vec<(Inputs, Assumptions)> = docs
+        .iter()
+        .map(|(_, doc)| {
+            (
+                doc.inputs.clone(),
+                doc.assumptions.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    let mut last_pct = None;
+    let mut print_progress = |done: usize, total: usize| {
+        let pct = done * 100 / total;
+        if last_pct != Some(pct) {
+            last_pct = Some(pct);
+            eprint!("\rprogress: {pct:>3}%");
+        }
+    };
+    let summaries = compute_summaries(
+        &samples,
+        args.progress
+            .then_some(&mut print_progress as &mut dyn FnMut(usize, usize)),
+    );
+    if args.progress && !samples.is_empty() {
+        eprintln!();
+    }
+
+    docs.into_iter()
+        .zip(summaries)
+        .map(|((idx, doc), summary)| {
+            validate_finite_output(&summary).map_err(at_line(idx))?;
+            Ok((doc, summary))
+        })
+        .collect()
+}
+
+/// `--output sqlite FILE` destination, if given.
+#[cfg(feature = "sqlite")]
+pub fn sqlite_output(args: &Args) -> Result<Option<&str>, AppError> {
+    match args.output.as_slice() {
+        [] => Ok(None),
+        [kind, path] if kind == "sqlite" => Ok(Some(path.as_str())),
+        [kind, ..] => Err(AppError::UnsupportedOutput { kind: kind.clone() }),
+    }
+}
+
+/// Run the `--batch` documents and insert one row per sample into the
+/// `results` table of the SQLite database at `path` (created if missing).
+/// All rows are written in a single transaction; returns the row count.
+#[cfg(feature = "sqlite")]
+pub fn write_batch_sqlite(args: &Args, path: &str) -> Result<usize, AppError> {
+    let rows = evaluate_batch(args)?;
+    let db_err = |source| AppError::Sqlite {
+        path: path.to_owned(),
+        source,
+    };
+    let mut conn = rusqlite::Connection::open(path).map_err(db_err)?;
+    let tx = conn.transaction().map_err(db_err)?;
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS results (
+            id TEXT,
+            timestamp TEXT,
+            sp REAL NOT NULL,
+            sa REAL NOT NULL,
+            density_kg_per_m3 REAL NOT NULL,
+            sg_20_20 REAL NOT NULL,
+            sg_25_25 REAL NOT NULL
+        )",
+        [],
+    )
+    .map_err(db_err)?;
+    {
+        let mut insert = tx
+            .prepare(
+                "INSERT INTO results (id, timestamp, sp, sa, density_kg_per_m3, sg_20_20, sg_25_25)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )
+            .map_err(db_err)?;
+        for (doc, out) in &rows {
+            insert
+                .execute(rusqlite::params![
+                    doc.id,
+                    doc.timestamp,
+                    out.sp,
+                    out.sa,
+                    out.density_kg_per_m3,
+                    out.sg_20_20,
+                    out.sg_25_25,
+                ])
+                .map_err(db_err)?;
+        }
+    }
+    tx.commit().map_err(db_err)?;
+    Ok(rows.len())
+}
+
 type CalculationOutput = CalculationSummary;
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct CmdInput {
     inputs: Inputs,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     assumptions: Option<Assumptions>,
+    /// Caller-side sample identifier, echoed in `--batch` output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    /// Caller-side sample time (any string), echoed in `--batch` output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+}
+
+/// Generate `count` standard-ratio seawater documents with SP drawn uniformly
+/// from `sp_range` using a seeded RNG, so identical arguments always produce
+/// identical output.
+#[cfg(feature = "rand")]
+pub fn generate_documents(args: &GenerateArgs) -> Result<Vec<String>, AppError> {
+    use crate::salinity::standard::standard_seawater;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let (min, max) = (args.sp_range[0], args.sp_range[1]);
+    if !(min.is_finite() && max.is_finite()) || min < 0.0 || min > max {
+        return Err(AppError::InvalidSpRange { min, max });
+    }
+
+    let ass = Assumptions::default();
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    (0..args.count)
+        .map(|_| {
+            let sp = if max > min {
+                rng.random_range(min..=max)
+            } else {
+                min
+            };
+            let doc = CmdInput {
+                inputs: standard_seawater(sp, &ass),
+                assumptions: None,
+                id: None,
+                timestamp: None,
+            };
+            serde_json::to_string(&doc).map_err(|source| AppError::SerializeOutput { source })
+        })
+        .collect()
 }
 
-pub fn print_output(out: &CalculationOutput, args: &Args) -> Result<(), AppError> {
+pub fn print_output(
+    out: &CalculationOutput,
+    components: Option<&Components>,
+    warnings: &[Warning],
+    ass: &Assumptions,
+    args: &Args,
+) -> Result<(), AppError> {
     validate_finite_output(out)?;
 
-    if args.json {
-        let s = serde_json::to_string_pretty(&out)
+    let format = if args.json {
+        OutputFormat::Json
+    } else {
+        args.format
+    };
+    if format == OutputFormat::Json {
+        let s = serde_json::to_string_pretty(&summary_json(out, &args.fields, args.with_units)?)
             .map_err(|source| AppError::SerializeOutput { source })?;
         println!("{}", s);
+    } else if format == OutputFormat::Woce {
+        println!("{}", woce_record(out, ass));
+    } else if format == OutputFormat::Markdown {
+        print!("{}", markdown_tables(out, components, warnings, ass));
     } else {
-        println!("SP: {:.4}", out.sp);
-        println!("SA: {:.4} g/kg", out.sa);
-        println!("Density: {:.3} kg/m^3", out.density_kg_per_m3);
-        println!("SG 20/20: {:.5}", out.sg_20_20);
-        println!("SG 25/25: {:.5}", out.sg_25_25);
+        let color = color_enabled(args);
+        let digits = ass.precision;
+        let sp_flagged = warnings
+            .iter()
+            .any(|w| matches!(w, Warning::OutOfRange { quantity: "SP", .. }));
+        println!(
+            "{}",
+            highlight(
+                &format!("SP: {:.*}", digits.sp, out.sp),
+                sp_flagged && color
+            )
+        );
+        println!("SA: {:.*} g/kg", digits.sa, out.sa);
+        if out.sa_anomaly_g_kg != 0.0 {
+            println!("SA anomaly: {:.*} g/kg", digits.sa, out.sa_anomaly_g_kg);
+        }
+        println!(
+            "Density: {:.*} kg/m^3",
+            digits.density, out.density_kg_per_m3
+        );
+        println!("SG 20/20: {:.*}", digits.sg, out.sg_20_20);
+        println!("SG 25/25: {:.*}", digits.sg, out.sg_25_25);
+        if let Some(c) = components {
+            print_components(c, digits.components, color);
+        }
+        if !warnings.is_empty() {
+            println!();
+            for w in warnings {
+                println!("{}", highlight(&format!("Warning: {w}"), color));
+            }
+        }
     }
 
     Ok(())
 }
 
+/// `out` as a JSON object, reduced to the `fields` keys when any are given
+/// (`--fields`). An unknown name is an error listing the valid ones. With
+/// `with_units` (`--with-units`) every value becomes
+/// `{"value": ..., "unit": ...}`, see `summary_unit`.
+fn summary_json(
+    out: &CalculationOutput,
+    fields: &[String],
+    with_units: bool,
+) -> Result<Value, AppError> {
+    let value = serde_json::to_value(out).map_err(|source| AppError::SerializeOutput { source })?;
+    let Value::Object(all) = value else {
+        return Ok(value);
+    };
+    let selected = if fields.is_empty() {
+        all
+    } else {
+        fields
+            .iter()
+            .map(|name| {
+                all.get(name)
+                    .map(|v| (name.clone(), v.clone()))
+                    .ok_or_else(|| AppError::UnknownField {
+                        name: name.clone(),
+                        valid: all
+                            .keys()
+                            .map(String::as_str)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    })
+            })
+            .collect::<Result<serde_json::Map<_, _>, _>>()?
+    };
+    if !with_units {
+        return Ok(Value::Object(selected));
+    }
+    Ok(Value::Object(
+        selected
+            .into_iter()
+            .map(|(name, value)| {
+                let unit = summary_unit(&name);
+                (name, serde_json::json!({ "value": value, "unit": unit }))
+            })
+            .collect(),
+    ))
+}
+
+/// Unit of a summary field in `--with-units` output; specific gravities are
+/// dimensionless (`"1"`).
+pub fn summary_unit(field: &str) -> &'static str {
+    match field {
+        "sp" | "normalized_sp" => "PSS-78",
+        "sa" | "sa_anomaly_g_kg" | "sa_composition_g_kg" => "g/kg",
+        "density_kg_per_m3" => "kg/m^3",
+        "normalized_ion_table" => "mg/L",
+        _ => "1",
+    }
+}
+
+/// Byte length of a `woce_record` line (without the newline).
+pub const WOCE_RECORD_LEN: usize = 47;
+
+/// Fixed-width exchange record, right-aligned fields, no separators:
+///
+/// | columns | width | field                | format |
+/// |---------|-------|----------------------|--------|
+/// | 1–10    | 10    | SP                   | F10.4  |
+/// | 11–20   | 10    | SA (g/kg)            | F10.4  |
+/// | 21–28   | 8     | temperature (°C)     | F8.3   |
+/// | 29–37   | 9     | pressure (dbar)      | F9.1   |
+/// | 38–47   | 10    | density (kg/m³)      | F10.3  |
+pub fn woce_record(out: &CalculationOutput, ass: &Assumptions) -> String {
+    format!(
+        "{:>10.4}{:>10.4}{:>8.3}{:>9.1}{:>10.3}",
+        out.sp, out.sa, ass.temp, ass.pressure_dbar, out.density_kg_per_m3
+    )
+}
+
+/// Summary and, when present, component tables as GitHub-flavored Markdown
+/// with padded, aligned columns (numbers right-aligned), followed by the
+/// warnings as a list. Estimated species are marked `\*` as in the human
+/// output.
+pub fn markdown_tables(
+    out: &CalculationOutput,
+    components: Option<&Components>,
+    warnings: &[Warning],
+    ass: &Assumptions,
+) -> String {
+    let digits = ass.precision;
+    let mut summary = vec![
+        ["SP".to_owned(), format!("{:.*}", digits.sp, out.sp)],
+        ["SA (g/kg)".to_owned(), format!("{:.*}", digits.sa, out.sa)],
+    ];
+    if out.sa_anomaly_g_kg != 0.0 {
+        summary.push([
+            "SA anomaly (g/kg)".to_owned(),
+            format!("{:.*}", digits.sa, out.sa_anomaly_g_kg),
+        ]);
+    }
+    summary.extend([
+        [
+            "Density (kg/m^3)".to_owned(),
+            format!("{:.*}", digits.density, out.density_kg_per_m3),
+        ],
+        [
+            "SG 20/20".to_owned(),
+            format!("{:.*}", digits.sg, out.sg_20_20),
+        ],
+        [
+            "SG 25/25".to_owned(),
+            format!("{:.*}", digits.sg, out.sg_25_25),
+        ],
+    ]);
+    let mut md = markdown_table(["Quantity", "Value"], &summary);
+
+    if let Some(c) = components {
+        let rows: Vec<[String; 3]> = c
+            .mg_l
+            .iter()
+            .zip(&c.mgkg)
+            .map(|((name, mg_l), (_, mgkg))| {
+                let mark = if c.is_estimated(name) { "\\*" } else { "" };
+                [
+                    format!("{name}{mark}"),
+                    format!("{:.*}", digits.components, mg_l),
+                    format!("{:.*}", digits.components, mgkg),
+                ]
+            })
+            .collect();
+        md.push('\n');
+        md.push_str(&markdown_table(["Species", "mg/L", "mg/kg"], &rows));
+        if c.estimated.iter().any(|(_, est)| *est) {
+            md.push_str("\n\\* estimated or assumed default, not measured\n");
+        }
+    }
+    if !warnings.is_empty() {
+        md.push('\n');
+        for w in warnings {
+            md.push_str(&format!("- Warning: {w}\n"));
+        }
+    }
+    md
+}
+
+/// One Markdown table; every column is padded to its widest cell, the first
+/// left-aligned and the rest right-aligned.
+fn markdown_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let widths: Vec<usize> = (0..N)
+        .map(|i| {
+            rows.iter()
+                .fold(header[i].chars().count().max(3), |w, row| {
+                    w.max(row[i].chars().count())
+                })
+        })
+        .collect();
+    let line = |cells: [&str; N]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if i == 0 {
+                    format!(" {cell:<w$} ", w = widths[i])
+                } else {
+                    format!(" {cell:>w$} ", w = widths[i])
+                }
+            })
+            .collect();
+        format!("|{}|\n", cells.join("|"))
+    };
+    let separator: Vec<String> = widths
+        .iter()
+        .enumerate()
+        .map(|(i, &w)| {
+            if i == 0 {
+                "-".repeat(w + 2)
+            } else {
+                format!("{}:", "-".repeat(w + 1))
+            }
+        })
+        .collect();
+
+    let mut table = line(header);
+    table.push_str(&format!("|{}|\n", separator.join("|")));
+    for row in rows {
+        table.push_str(&line(row.each_ref().map(String::as_str)));
+    }
+    table
+}
+
+/// Print the mg/L and mg/kg component tables; estimated rows are marked `*`
+/// (and highlighted when `color` is set).
+fn print_components(c: &Components, digits: usize, color: bool) {
+    println!();
+    println!("{:<9} {:>12} {:>12}", "Species", "mg/L", "mg/kg");
+    for ((name, mg_l), (_, mgkg)) in c.mg_l.iter().zip(&c.mgkg) {
+        let estimated = c.is_estimated(name);
+        let mark = if estimated { "*" } else { "" };
+        let row = format!(
+            "{:<9} {:>12.*} {:>12.*}",
+            format!("{name}{mark}"),
+            digits,
+            mg_l,
+            digits,
+            mgkg
+        );
+        println!("{}", highlight(&row, estimated && color));
+    }
+    if c.estimated.iter().any(|(_, est)| *est) {
+        println!("* estimated or assumed default, not measured");
+    }
+}
+
+#[cfg(feature = "color")]
+fn color_enabled(args: &Args) -> bool {
+    use std::io::IsTerminal;
+    match args.color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => std::env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal(),
+    }
+}
+
+#[cfg(not(feature = "color"))]
+fn color_enabled(_args: &Args) -> bool {
+    false
+}
+
+/// Render `text` in the warning color when `on`.
+#[cfg(feature = "color")]
+fn highlight(text: &str, on: bool) -> String {
+    use owo_colors::OwoColorize;
+    if on {
+        text.yellow().to_string()
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(not(feature = "color"))]
+fn highlight(text: &str, _on: bool) -> String {
+    text.to_owned()
+}
+
 fn validate_finite_output(out: &CalculationOutput) -> Result<(), AppError> {
     let values = [
         out.sp,
         out.sa,
+        out.sa_anomaly_g_kg,
         out.density_kg_per_m3,
         out.sg_20_20,
         out.sg_25_25,
+        out.normalized_sp,
     ];
-    if values.into_iter().all(f64::is_finite) {
+    let table = out
+        .normalized_ion_table
+        .iter()
+        .map(|(_, v)| *v)
+        .chain(out.sa_composition_g_kg);
+    if values.into_iter().chain(table).all(f64::is_finite) {
         Ok(())
     } else {
         Err(AppError::NonFiniteOutput)
@@ -1,26 +1,236 @@
-//! Approximate manual implementation of TEOS-10 conservative temperature conversion.
-//! Enabled behind the `approx_ct` feature.
+//! Pure-Rust TEOS-10 conservative temperature from in-situ temperature.
 //!
-//! This is a reduced approximation: CT ≈ t - Δ(adiabatic lapse) for shallow pressures.
-//! It is NOT a full TEOS-10 polynomial port. Error expected <~0.02 K for p < 50 dbar.
-//! For precise thermodynamic work port full GSW routines.
+//! Port of `gsw_ct_from_t`: potential temperature `pt0` from the entropy
+//! balance (`gsw_pt0_from_t`), then Conservative Temperature from the
+//! potential-enthalpy polynomial (`gsw_ct_from_pt`). With the default two
+//! Newton steps `pt0` satisfies the entropy balance to ~1e-13 K. The
+//! `approx_ct` feature stops after one step (error below 1e-6 K up to
+//! 10000 dbar); CT-from-pt is exact in both cases.
 
-/// Approximate potential temperature from in-situ temperature.
-/// Very rough UNESCO-like lapse correction: θ ≈ t - 1.0e-4 * p_dbar * t.
-pub fn potential_temperature_approx(_sa: f64, t: f64, p_dbar: f64) -> f64 {
-    t - 1.0e-4 * p_dbar * t
+/// TEOS-10 constants (from GSW_TEOS10_CONSTANTS), duplicated here to avoid
+/// relying on internal symbol names of the upstream `gsw` crate.
+const GSW_SFAC: f64 = 0.024_882_667_558_461_5;
+const GSW_CP0: f64 = 3_991.867_957_119_63; // J/(kg·K)
+const GSW_T0: f64 = 273.15; // K
+const GSW_SSO: f64 = 35.165_04; // Standard Ocean Salinity
+const GSW_UPS: f64 = 35.0; // Reference Practical Salinity divisor
+
+/// Reference-salinity constants used by `pt0_from_t_with_config` and
+/// `ct_from_t_manual_with_config`.
+///
+/// They set the reduced salinity `x² = SA · ups / (40 · sso)` of the Gibbs
+/// function (`GSW_SFAC` for the defaults) in the entropy, its temperature
+/// derivative and the potential-enthalpy polynomial, as well as the `pt0`
+/// first guess. A different SSO therefore rescales the salinity dependence:
+/// SSO = 36 g/kg raises CT at SA = 35 g/kg, θ = 10 °C by about 0.015 K.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Teos10Config {
+    /// Standard Ocean Reference Salinity (g/kg).
+    pub sso: f64,
+    /// Practical Salinity divisor of the reduced salinity `SA / ups`.
+    pub ups: f64,
+}
+
+impl Default for Teos10Config {
+    fn default() -> Self {
+        Self {
+            sso: GSW_SSO,
+            ups: GSW_UPS,
+        }
+    }
+}
+
+impl Teos10Config {
+    /// Reduced-salinity factor `ups / (40 · sso)`; exactly `GSW_SFAC` for
+    /// the defaults.
+    fn sfac(&self) -> f64 {
+        GSW_SFAC * (GSW_SSO / self.sso) * (self.ups / GSW_UPS)
+    }
+}
+
+/// Newton steps refining the `pt0` first guess.
+const PT0_NEWTON_STEPS: usize = if cfg!(feature = "approx_ct") { 1 } else { 2 };
+
+/// Conservative Temperature (°C) from potential temperature (TEOS-10
+/// `gsw_ct_from_pt`).
+pub fn ct_from_pt(sa: f64, pt: f64) -> f64 {
+    ct_from_pt_with_config(sa, pt, &Teos10Config::default())
 }
 
-/// Conservative temperature approximation from potential temperature.
-/// Here CT ≈ θ (identity) for the reduced model.
-pub fn conservative_temperature_from_pt_approx(_sa: f64, pt: f64) -> f64 {
-    pt
+/// `ct_from_pt` with explicit reference-salinity constants.
+#[allow(clippy::excessive_precision)]
+pub fn ct_from_pt_with_config(sa: f64, pt: f64, cfg: &Teos10Config) -> f64 {
+    let x2 = cfg.sfac() * sa.max(0.0);
+    let x = x2.sqrt();
+    let y = pt * 0.025;
+    let pot_enthalpy = 61.013_624_206_810_71
+        + y * (168_776.461_380_480_15
+            + y * (-2_735.278_560_511_962_5
+                + y * (2_574.216_445_382_143_3
+                    + y * (-1_536.664_443_497_754_3
+                        + y * (545.734_049_793_162_9
+                            + (-50.910_917_284_743_31 - 18.304_898_789_278_02 * y) * y)))))
+        + x2 * (268.552_026_584_507_1
+            + y * (-12_019.028_203_559_312
+                + y * (3_734.858_026_725_145
+                    + y * (-2_046.767_114_505_761_8
+                        + y * (465.286_556_238_262_34
+                            + (-0.637_082_030_237_635_9 - 10.650_848_542_359_153 * y) * y))))
+            + x * (937.209_911_062_070_7
+                + y * (588.180_281_217_010_8
+                    + y * (248.394_765_229_712_85
+                        + (-3.871_557_904_936_333 - 2.626_801_985_426_835_6 * y) * y))
+                + x * (-1_687.914_374_187_449
+                    + x * (246.959_888_878_137_7
+                        + x * (123.595_765_824_579_64 - 48.589_106_902_540_9 * x))
+                    + y * (936.320_654_446_033_6
+                        + y * (-942.782_730_454_443_9
+                            + y * (369.438_943_750_900_2
+                                + (-33.836_649_478_952_48 - 9.987_880_382_780_322 * y) * y))))));
+    pot_enthalpy / GSW_CP0
 }
 
-/// Combined helper mirroring ct_from_t signature in teos10.rs.
+/// Conservative Temperature (°C) from SA (g/kg), in-situ `t` (°C) and
+/// sea pressure (dbar); mirrors `teos10::ct_from_t` without its clamping.
 pub fn ct_from_t_manual(sa: f64, t: f64, p_dbar: f64) -> f64 {
-    let pt = potential_temperature_approx(sa, t, p_dbar);
-    conservative_temperature_from_pt_approx(sa, pt)
+    ct_from_t_manual_with_config(sa, t, p_dbar, &Teos10Config::default())
+}
+
+/// `ct_from_t_manual` with explicit reference-salinity constants.
+pub fn ct_from_t_manual_with_config(sa: f64, t: f64, p_dbar: f64, cfg: &Teos10Config) -> f64 {
+    ct_from_pt_with_config(sa, pt0_from_t_with_config(sa, t, p_dbar, cfg), cfg)
+}
+
+/// Gibbs second derivative with respect to temperature at p=0.
+#[allow(clippy::excessive_precision)]
+fn gibbs_pt0_pt0(sa: f64, pt0: f64, sfac: f64) -> f64 {
+    let x2 = sfac * sa;
+    let x = x2.sqrt();
+    let y = pt0 * 0.025;
+
+    let g03 = -24_715.571_866_078
+        + y * (4_420.447_224_909_672_5
+            + y * (-1_778.231_237_203_896
+                + y * (1_160.518_251_685_141_9
+                    + y * (-569.531_539_542_516 + y * 128.134_291_524_946_15))));
+    let g08 = x2
+        * (1_760.062_705_994_408
+            + x * (-86.132_935_195_608_4
+                + x * (-137.114_501_840_898_2
+                    + y * (296.200_616_913_752_36
+                        + y * (-205.677_092_903_745_63 + 49.939_401_913_901_6 * y)))
+                + y * (-60.136_422_517_125 + y * 10.507_207_941_707_34))
+            + y * (-1_351.605_895_580_406
+                + y * (1_097.112_537_301_510_9
+                    + y * (-433.206_481_750_622_06 + 63.905_091_254_154_904 * y))));
+    (g03 + g08) * 0.000_625
+}
+
+/// Entropy part at given pressure (TEOS-10 `gsw_entropy_part`).
+#[allow(clippy::excessive_precision)]
+fn entropy_part(sa: f64, t: f64, p_dbar: f64, sfac: f64) -> f64 {
+    let x2 = sfac * sa;
+    let x = x2.sqrt();
+    let y = t * 0.025;
+    let z = p_dbar * 1e-4;
+
+    let g03 = z
+        * (-270.983_805_184_062
+            + z * (776.153_611_613_101
+                + z * (-196.512_550_881_22
+                    + (28.979_652_629_417_5 - 2.132_900_835_183_27 * z) * z)))
+        + y * (-24_715.571_866_078
+            + z * (2_910.072_908_093_6
+                + z * (-1_513.116_771_538_718
+                    + z * (546.959_324_647_056
+                        + z * (-111.120_812_763_443_6 + 8.688_413_438_343_94 * z))))
+            + y * (2_210.223_612_454_836_3
+                + z * (-2_017.523_349_435_21
+                    + z * (1_498.081_172_457_456
+                        + z * (-718.635_991_963_235_9
+                            + (146.403_755_578_161_6 - 4.989_213_186_267_150_5 * z) * z)))
+                + y * (-592.743_745_734_632
+                    + z * (1_591.873_781_627_888
+                        + z * (-1_207.261_522_487_504
+                            + (608.785_486_935_364 - 105.499_350_893_120_8 * z) * z))
+                    + y * (290.129_562_921_285_47
+                        + z * (-973.091_553_087_975
+                            + z * (602.603_274_510_125
+                                + z * (-276.361_526_170_076 + 32.409_533_403_861_05 * z)))
+                        + y * (-113.906_307_908_503_21
+                            + y * (21.355_715_254_157_69 - 67.417_568_357_514_34 * z)
+                            + z * (381.068_361_985_070_96
+                                + z * (-133.738_390_284_275_4 + 49.023_632_509_086_724 * z)))))));
+
+    let g08 = x2
+        * (z * (729.116_529_735_046
+            + z * (-343.956_902_961_561
+                + z * (124.687_671_116_248
+                    + z * (-31.656_964_386_073 + 7.046_588_033_154_49 * z))))
+            + x * (x
+                * (y * (-137.114_501_840_898_2
+                    + y * (148.100_308_456_876_18
+                        + y * (-68.559_030_967_915_2 + 12.484_850_478_475_4 * y)))
+                    - 22.668_355_851_282_9 * z)
+                + z * (-175.292_041_186_547
+                    + (83.192_392_780_181_9 - 29.483_064_349_429 * z) * z)
+                + y * (-86.132_935_195_608_4
+                    + z * (766.116_132_004_952
+                        + z * (-108.383_452_503_422_4 + 51.279_697_477_982_8 * z))
+                    + y * (-30.068_211_258_562_5 - 1_380.959_795_403_770_8 * z
+                        + y * (3.502_402_647_235_78 + 938.260_750_445_42 * z))))
+            + y * (1_760.062_705_994_408
+                + y * (-675.802_947_790_203
+                    + y * (365.704_179_100_503_6
+                        + y * (-108.301_620_437_655_52 + 12.781_018_250_830_98 * y)
+                        + z * (-1_190.914_967_948_748
+                            + (298.904_564_555_024 - 145.949_167_600_635_2 * z) * z))
+                    + z * (2_082.734_442_399_804_3
+                        + z * (-614.668_925_894_709
+                            + (340.685_093_521_782 - 33.384_820_297_923_9 * z) * z)))
+                + z * (-1_721.528_607_567_954
+                    + z * (674.819_060_538_734
+                        + z * (-356.629_112_415_276
+                            + (88.408_071_661_6 - 15.840_030_944_233_64 * z) * z)))));
+
+    -(g03 + g08) * 0.025
+}
+
+fn entropy_part_zerop(sa: f64, pt0: f64, sfac: f64) -> f64 {
+    entropy_part(sa, pt0, 0.0, sfac)
+}
+
+/// Potential temperature (°C) referenced to p = 0 (TEOS-10 `gsw_pt0_from_t`).
+pub fn pt0_from_t(sa: f64, t: f64, p_dbar: f64) -> f64 {
+    pt0_from_t_with_config(sa, t, p_dbar, &Teos10Config::default())
+}
+
+/// `pt0_from_t` with explicit reference-salinity constants.
+#[allow(clippy::excessive_precision)]
+pub fn pt0_from_t_with_config(sa: f64, t: f64, p_dbar: f64, cfg: &Teos10Config) -> f64 {
+    let s1 = sa / cfg.ups;
+    let mut pt0 = t + p_dbar
+        * (8.654_839_133_954_42e-6
+            - s1 * 1.416_362_997_448_81e-6
+            - p_dbar * 7.382_864_671_357_37e-9
+            + t * (-8.382_413_570_396_98e-6
+                + s1 * 2.839_333_685_855_34e-8
+                + t * 1.778_039_652_186_56e-8
+                + p_dbar * 1.711_556_192_082_33e-10));
+
+    let mut dentropy_dt = GSW_CP0 / ((GSW_T0 + pt0) * (1.0 - 0.05 * (1.0 - sa / cfg.sso)));
+    let sfac = cfg.sfac();
+    let true_entropy_part = entropy_part(sa, t, p_dbar, sfac);
+
+    for _ in 0..PT0_NEWTON_STEPS {
+        let pt0_old = pt0;
+        let dentropy = entropy_part_zerop(sa, pt0_old, sfac) - true_entropy_part;
+        pt0 = pt0_old - dentropy / dentropy_dt;
+        let pt0m = 0.5 * (pt0 + pt0_old);
+        dentropy_dt = -gibbs_pt0_pt0(sa, pt0m, sfac);
+        pt0 = pt0_old - dentropy / dentropy_dt;
+    }
+    pt0
 }
 
 #[cfg(test)]
@@ -28,18 +238,81 @@ mod tests {
     use super::*;
 
     #[test]
-    fn shallow_pressure_small_difference() {
-        let sa = 35.0 * 35.16504 / 35.0; // ~SR_REF
-        let t = 20.0;
-        let p = 10.0; // 10 dbar
-        let ct = ct_from_t_manual(sa, t, p);
-        // Expect CT only slightly below t
-        assert!((t - ct) > 0.0 && (t - ct) < 0.05);
+    fn ct_from_pt_matches_gsw() {
+        for (sa, pt) in [
+            (32.0, 10.0),
+            (35.165_04, 20.0),
+            (0.0, 4.0),
+            (40.0, -1.5),
+            (120.0, 35.0),
+        ] {
+            let reference = gsw::conversions::ct_from_pt(sa, pt).unwrap();
+            assert!(
+                (ct_from_pt(sa, pt) - reference).abs() < 1e-12,
+                "SA {sa}, pt {pt}: {} vs {reference}",
+                ct_from_pt(sa, pt)
+            );
+        }
+    }
+
+    #[test]
+    fn ct_from_t_matches_reference_values() {
+        // (SA g/kg, t °C, p dbar) -> CT °C. The first row is the GSW check
+        // value; the others use pt0 verified against the full TEOS-10 Gibbs
+        // function (entropy balance to 1e-14 K). The deep rows bound the
+        // one-step `approx_ct` error documented above.
+        let tol = if cfg!(feature = "approx_ct") {
+            1e-6
+        } else {
+            1e-9
+        };
+        for (sa, t, p, ct) in [
+            (34.7118, 28.7856, 10.0, 28.809_919_826_700_28),
+            (34.8915, 28.4329, 50.0, 28.439_227_816_091_14),
+            (35.0256, 22.8103, 125.0, 22.786_176_893_078_487),
+            (34.8472, 10.2600, 250.0, 10.226_189_266_620_782),
+            (34.7366, 6.8863, 600.0, 6.827_213_633_479_987),
+            (34.7324, 4.4036, 1000.0, 4.323_575_748_610_454),
+            (34.7, 1.5, 5000.0, 1.067_184_988_941_72),
+            (34.7, 1.5, 10_000.0, 0.322_883_217_799_679_9),
+        ] {
+            let got = ct_from_t_manual(sa, t, p);
+            assert!(
+                (got - ct).abs() < tol,
+                "SA {sa}, t {t}, p {p}: {got} vs {ct}"
+            );
+        }
+    }
+
+    #[test]
+    fn teos10_config_defaults_reproduce_pt0_and_perturbations_propagate() {
+        let cfg = Teos10Config::default();
+        for (sa, t, p) in [
+            (35.0, 10.0, 1000.0),
+            (34.7, 2.0, 5000.0),
+            (40.0, 25.0, 50.0),
+        ] {
+            assert_eq!(pt0_from_t_with_config(sa, t, p, &cfg), pt0_from_t(sa, t, p));
+            assert_eq!(
+                ct_from_t_manual_with_config(sa, t, p, &cfg),
+                ct_from_t_manual(sa, t, p)
+            );
+        }
+
+        // A larger SSO lowers the reduced salinity of the Gibbs function,
+        // which shifts both pt0 at depth and CT by a measurable amount.
+        let perturbed = Teos10Config { sso: 36.0, ..cfg };
+        let (sa, t, p) = (34.7, 2.0, 5000.0);
+        let pt0_shift = pt0_from_t_with_config(sa, t, p, &perturbed) - pt0_from_t(sa, t, p);
+        assert!(pt0_shift.abs() > 1e-6, "pt0 shift {pt0_shift}");
+        let ct_shift = ct_from_pt_with_config(35.0, 10.0, &perturbed) - ct_from_pt(35.0, 10.0);
+        assert!((1e-3..0.1).contains(&ct_shift.abs()), "CT shift {ct_shift}");
     }
 
     #[test]
-    fn zero_pressure_identity() {
-        let ct = ct_from_t_manual(35.0, 18.0, 0.0);
-        assert!((ct - 18.0).abs() < 1e-12);
+    fn surface_ct_differs_from_t_only_through_salinity() {
+        // At p = 0, pt0 == t and CT is pot. enthalpy / cp0.
+        assert!((pt0_from_t(35.0, 18.0, 0.0) - 18.0).abs() < 1e-10);
+        assert!((ct_from_t_manual(35.0, 18.0, 0.0) - ct_from_pt(35.0, 18.0)).abs() < 1e-10);
     }
 }
@@ -0,0 +1,32 @@
+//! Analytic stand-in for the TEOS-10 density and CT routines
+//! (`mock-teos10` feature). **Not physical.**
+//!
+//! A linear equation of state
+//! `ρ = RHO0 · (1 − ALPHA · (CT − CT0) + BETA · (SA − SA0))` with CT equal to
+//! the in-situ temperature and no pressure dependence. It is exact enough to
+//! exercise the solver deterministically (and invert by hand in tests) but is
+//! off by several kg/m³ away from the reference point; never use it for real
+//! samples.
+
+/// Density at the reference point (kg/m³).
+pub const RHO0: f64 = 1027.0;
+/// Reference Conservative Temperature (°C).
+pub const CT0: f64 = 10.0;
+/// Reference Absolute Salinity (g/kg).
+pub const SA0: f64 = 35.0;
+/// Thermal expansion coefficient (1/K).
+pub const ALPHA: f64 = 1.7e-4;
+/// Haline contraction coefficient (kg/g).
+pub const BETA: f64 = 7.6e-4;
+
+/// Linear-EOS density (kg/m³); `p_dbar` is ignored. `None` for non-finite or
+/// non-positive results.
+pub fn try_rho(sa: f64, ct: f64, _p_dbar: f64) -> Option<f64> {
+    let rho = RHO0 * (1.0 - ALPHA * (ct - CT0) + BETA * (sa - SA0));
+    (rho.is_finite() && rho > 0.0).then_some(rho)
+}
+
+/// Conservative Temperature under the mock: the in-situ temperature itself.
+pub fn ct_from_t(_sa: f64, temp: f64, _p_dbar: f64) -> f64 {
+    temp
+}
@@ -1,10 +1,10 @@
+pub mod brine;
 #[cfg(feature = "cli")]
 pub mod cli;
-#[cfg(feature = "approx_ct")]
 pub mod manual_ct;
+#[cfg(feature = "mock-teos10")]
+pub mod mock_teos10;
 pub mod teos10;
-#[cfg(not(feature = "approx_ct"))]
-pub mod teos10_reduced;
 
 #[cfg(feature = "cli")]
 use clap::Parser;
@@ -12,14 +12,42 @@ use clap::Parser;
 #[cfg(feature = "cli")]
 pub fn run() -> Result<(), crate::error::AppError> {
     use crate::adapters::cli::{Args, parse_inputs};
-    use crate::salinity::calculator::compute_summary;
+    use crate::salinity::calculator::compute_full;
 
     let args = Args::parse();
+
+    #[cfg(feature = "rand")]
+    if let Some(crate::adapters::cli::Command::Generate(gen_args)) = &args.command {
+        for doc in crate::adapters::cli::generate_documents(gen_args)? {
+            println!("{}", doc);
+        }
+        return Ok(());
+    }
+
+    if args.batch {
+        #[cfg(feature = "sqlite")]
+        if let Some(path) = crate::adapters::cli::sqlite_output(&args)? {
+            crate::adapters::cli::write_batch_sqlite(&args, path)?;
+            return Ok(());
+        }
+        for line in crate::adapters::cli::run_batch(&args)? {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
     let (base_inp, ass) = parse_inputs(&args)?;
 
-    let out = compute_summary(&base_inp, &ass);
+    let full = compute_full(&base_inp, &ass);
 
-    crate::adapters::cli::print_output(&out, &args)?;
+    // Report the conditions the sample was computed at (`t_c`/`p_dbar`).
+    crate::adapters::cli::print_output(
+        &full.summary,
+        full.components.as_ref(),
+        &full.warnings,
+        &ass.at_sample_conditions(&base_inp),
+        &args,
+    )?;
 
     Ok(())
 }
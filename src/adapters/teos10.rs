@@ -1,9 +1,20 @@
-#[cfg(feature = "approx_ct")]
-use crate::adapters::manual_ct::ct_from_t_manual;
-#[cfg(not(feature = "approx_ct"))]
-use crate::adapters::teos10_reduced::{ct_from_pt, pt0_from_t};
+pub use crate::adapters::manual_ct::Teos10Config;
 use gsw as gsw_teos10;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Lowest in-situ temperature (°C) accepted by `ct_from_t`.
+///
+/// Covers supercooled seawater and sea-ice brines (freezing point of SA≈100 g/kg
+/// brine is about −5.9 °C); colder inputs lie outside the validated range of
+/// the pt0 iteration and give `NaN`.
+pub const T_MIN_C: f64 = -6.0;
+
+/// Validity range of the PSS-78 practical salinity scale.
+pub const SP_MIN_PSS78: f64 = 2.0;
+pub const SP_MAX_PSS78: f64 = 42.0;
+
 /// Absolute/Reference Salinity from Practical Salinity.
 /// Note: This returns TEOS-10 Reference Salinity (SR) from SP and is used
 /// as an approximation for Absolute Salinity (SA). For standard seawater
@@ -12,6 +23,127 @@ pub fn sa_from_sp(sp: f64) -> f64 {
     gsw_teos10::conversions::sr_from_sp(sp)
 }
 
+/// Conductivity of standard seawater C(SP=35, t68=15 °C, p=0) in mS/cm (PSS-78).
+pub const C3515_MS_CM: f64 = 42.914;
+
+/// Conductivity ratio R = C(SP, t, p) / C(35, 15, 0) from Practical Salinity
+/// (inverse PSS-78). `t90` is in-situ temperature (ITS-90, °C), `p_dbar` sea
+/// pressure. Returns `NaN` outside the PSS-78 range (SP < 0 or SP > 42).
+pub fn r_from_sp(sp: f64, t90: f64, p_dbar: f64) -> f64 {
+    gsw_teos10::practical_salinity::r_from_sp(sp, t90, p_dbar)
+        .ok()
+        .filter(|r| r.is_finite())
+        .unwrap_or(f64::NAN)
+}
+
+/// Electrical conductivity (mS/cm) of seawater at its measurement temperature
+/// and pressure, i.e. what a conductivity probe displays without temperature
+/// compensation: `R(SP, t, p) * C3515_MS_CM`.
+pub fn conductivity_ms_cm(sp: f64, t_c: f64, p_dbar: f64) -> f64 {
+    r_from_sp(sp, t_c, p_dbar) * C3515_MS_CM
+}
+
+/// Practical Salinity from the conductivity ratio R = C / C(35, 15, 0)
+/// (PSS-78). `t90` is in-situ temperature (ITS-90, °C), `p_dbar` sea
+/// pressure. Returns `NaN` for negative ratios or on `gsw` errors.
+pub fn sp_from_conductivity(c_ratio: f64, t90: f64, p_dbar: f64) -> f64 {
+    gsw_teos10::practical_salinity::sp_from_r(c_ratio, t90, p_dbar)
+        .ok()
+        .filter(|sp| sp.is_finite())
+        .unwrap_or(f64::NAN)
+}
+
+/// Practical Salinity from conductivity `c_ms_cm` (mS/cm) at in-situ
+/// temperature `t_c` (ITS-90, °C) and sea pressure `p_dbar`, as GSW
+/// `gsw_sp_from_c`: the conductivity is divided by `C3515_MS_CM` and passed to
+/// `sp_from_conductivity`. Negative conductivities are clamped to zero.
+pub fn sp_from_c(c_ms_cm: f64, t_c: f64, p_dbar: f64) -> f64 {
+    sp_from_conductivity(c_ms_cm.max(0.0) / C3515_MS_CM, t_c, p_dbar)
+}
+
+/// Conductivity (mS/cm) from Practical Salinity, the inverse of `sp_from_c`
+/// (GSW `gsw_c_from_sp`); same as `conductivity_ms_cm`.
+pub fn c_from_sp(sp: f64, t_c: f64, p_dbar: f64) -> f64 {
+    conductivity_ms_cm(sp, t_c, p_dbar)
+}
+
+/// Absolute Salinity (g/kg) from Practical Salinity at a position.
+///
+/// Inside the Baltic Sea this applies the TEOS-10 Baltic relation
+/// `SA = (SSO - 0.087) / 35 * SP + 0.087` (`gsw_sa_from_sp_baltic`). The
+/// open-ocean SAAR atlas is not bundled, so elsewhere this returns Reference
+/// Salinity, which differs from SA by at most ~0.025 g/kg in the deep North
+/// Pacific and much less near the surface. Returns `NaN` for positions or
+/// pressures outside the TEOS-10 domain (`lon` in 0..360 or −180..180).
+pub fn sa_from_sp_location(sp: f64, p_dbar: f64, lon: f64, lat: f64) -> f64 {
+    let lon = if lon < 0.0 { lon + 360.0 } else { lon };
+    if !(-1.5..=12_000.0).contains(&p_dbar)
+        || !(0.0..=360.0).contains(&lon)
+        || !(-90.0..=90.0).contains(&lat)
+    {
+        return f64::NAN;
+    }
+    if in_baltic(lon, lat) {
+        (GSW_SSO - 0.087) / 35.0 * sp + 0.087
+    } else {
+        sa_from_sp(sp)
+    }
+}
+
+/// Standard Ocean Reference Salinity (g/kg).
+const GSW_SSO: f64 = 35.165_04;
+
+/// Whether (`lon`, `lat`) lies inside the TEOS-10 Baltic Sea polygon.
+fn in_baltic(lon: f64, lat: f64) -> bool {
+    const LAT_LEFT: [f64; 3] = [50.0, 59.0, 69.0];
+    const LON_LEFT: [f64; 3] = [12.6, 7.0, 26.0];
+    const LAT_RIGHT: [f64; 3] = [50.0, 69.0, 69.0];
+    const LON_RIGHT: [f64; 3] = [45.0, 26.0, 45.0];
+    if !(LON_LEFT[1] < lon && lon < LON_RIGHT[0] && LAT_LEFT[0] < lat && lat < LAT_LEFT[2]) {
+        return false;
+    }
+    interp(&LAT_LEFT, &LON_LEFT, lat) <= lon && lon <= interp(&LAT_RIGHT, &LON_RIGHT, lat)
+}
+
+/// Piecewise-linear interpolation of `ys` over ascending `xs` at `x`.
+fn interp(xs: &[f64; 3], ys: &[f64; 3], x: f64) -> f64 {
+    let i = if x < xs[1] { 0 } else { 1 };
+    let w = if xs[i + 1] > xs[i] {
+        (x - xs[i]) / (xs[i + 1] - xs[i])
+    } else {
+        0.0
+    };
+    ys[i] + w * (ys[i + 1] - ys[i])
+}
+
+/// Absolute Salinity (g/kg) from a CTD conductivity ratio, in-situ
+/// temperature (°C), sea pressure (dbar) and position: `sp_from_conductivity`
+/// followed by `sa_from_sp_location`.
+pub fn sa_from_conductivity(c_ratio: f64, t_c: f64, p_dbar: f64, lon: f64, lat: f64) -> f64 {
+    sa_from_sp_location(sp_from_conductivity(c_ratio, t_c, p_dbar), p_dbar, lon, lat)
+}
+
+/// `(temperature, SP)` from `sp_from_conductivity` for one conductivity
+/// ratio `c_ratio` read at each temperature in `temps` (°C, ITS-90) and
+/// `p_dbar`; a diagnostic for conductivity sensors.
+///
+/// PSS-78 itself takes the temperature dependence of conductivity into
+/// account, so for one water sample the SP is flat across temperature as
+/// long as the ratio is the in-situ C / C(35, 15, 0) at each temperature.
+/// A ratio that stays fixed while the sample temperature changes is not
+/// in-situ (the probe already compensates for temperature), and the sweep
+/// then shows SP drifting by roughly 2 % per kelvin.
+pub fn sp_conductivity_temperature_sweep(
+    c_ratio: f64,
+    temps: &[f64],
+    p_dbar: f64,
+) -> Vec<(f64, f64)> {
+    temps
+        .iter()
+        .map(|&t| (t, sp_from_conductivity(c_ratio, t, p_dbar)))
+        .collect()
+}
+
 /// Computes Conservative Temperature (CT) from in-situ temperature `t` and Absolute Salinity `sa`.
 ///
 /// # Arguments
@@ -23,30 +155,210 @@ pub fn sa_from_sp(sp: f64) -> f64 {
 /// * Conservative Temperature [°C]
 ///
 /// # Features
-/// * If the feature `reduced_ct` is enabled, uses a fast manual approximation for CT.
-/// * Otherwise, calculates a reduced TEOS-10 potential temperature (`pt0`) and applies a simplified identity conversion (CT ≈ PT0).
+/// * By default, computes the TEOS-10 potential temperature (`pt0`) and then CT
+///   from the potential-enthalpy polynomial (see `manual_ct`).
+/// * With `approx_ct`, `pt0` stops after one Newton step (< 3e-5 K); the CT
+///   conversion is the same.
+///
+/// # Domain
+/// `temp` below `T_MIN_C` returns `NaN` (the calculation entry points then
+/// report `Warning::OutOfDomain`). Other out-of-range inputs are passed
+/// through so that failures surface as non-finite values.
+///
+/// With `mock-teos10`, returns the in-situ temperature unchanged (see
+/// `mock_teos10`; not physical).
 pub fn ct_from_t(sa: f64, temp: f64, p_dbar: f64) -> f64 {
-    #[cfg(feature = "approx_ct")]
-    {
-        ct_from_t_manual(sa, temp, p_dbar)
+    if temp < T_MIN_C {
+        return f64::NAN;
     }
 
-    #[cfg(not(feature = "approx_ct"))]
-    {
-        let pt0 = pt0_from_t(sa, temp, p_dbar);
-        ct_from_pt(sa, pt0)
+    #[cfg(feature = "mock-teos10")]
+    return crate::adapters::mock_teos10::ct_from_t(sa, temp, p_dbar);
+    #[cfg(not(feature = "mock-teos10"))]
+    crate::adapters::manual_ct::ct_from_t_manual(sa, temp, p_dbar)
+}
+
+/// `ct_from_t` with explicit reference-salinity constants, for probing the
+/// sensitivity to the reference salinity definition.
+///
+/// With `mock-teos10`, `cfg` is ignored like everything else in the mock.
+pub fn ct_from_t_with_config(sa: f64, temp: f64, p_dbar: f64, cfg: &Teos10Config) -> f64 {
+    #[cfg(feature = "mock-teos10")]
+    return {
+        let _ = cfg;
+        ct_from_t(sa, temp, p_dbar)
+    };
+    #[cfg(not(feature = "mock-teos10"))]
+    if temp < T_MIN_C {
+        f64::NAN
+    } else {
+        crate::adapters::manual_ct::ct_from_t_manual_with_config(sa, temp, p_dbar, cfg)
     }
 }
 
+/// Potential temperature (°C) referenced to 0 dbar (TEOS-10
+/// `gsw_pt0_from_t`) of seawater at Absolute Salinity `sa`, in-situ
+/// temperature `temp` (°C) and sea pressure `p_dbar`.
+///
+/// Shares the `manual_ct` port (and the `T_MIN_C` bound, below which it
+/// returns `NaN`) with `ct_from_t`; `mock-teos10` does not replace it.
+pub fn pt_from_t(sa: f64, temp: f64, p_dbar: f64) -> f64 {
+    if temp < T_MIN_C {
+        return f64::NAN;
+    }
+    crate::adapters::manual_ct::pt0_from_t(sa, temp, p_dbar)
+}
+
+/// Conservative Temperature (°C) at which seawater of Absolute Salinity `sa`
+/// (g/kg) freezes at sea pressure `p_dbar` (TEOS-10 `gsw_CT_freezing_poly`,
+/// accurate to ~6e-4 K for SA ≤ 120 g/kg and p ≤ 10000 dbar).
+///
+/// `saturation_fraction` is the dissolved-air saturation between 0
+/// (air-free) and 1 (air-saturated), which lowers the freezing point by up
+/// to ~2.4 mK.
+#[allow(clippy::excessive_precision)]
+pub fn ct_freezing(sa: f64, p_dbar: f64, saturation_fraction: f64) -> f64 {
+    const C: [f64; 23] = [
+        0.017_947_064_327_968_736,
+        -6.076_099_099_929_818,
+        4.883_198_653_547_851,
+        -11.880_816_012_305_42,
+        13.346_585_114_802_57,
+        -8.722_761_043_208_607,
+        2.082_038_908_808_201,
+        -7.389_420_998_107_497,
+        -2.110_913_185_058_476,
+        0.229_549_157_800_622_9,
+        -0.989_153_812_330_728_2,
+        -0.089_871_501_284_064_96,
+        0.383_113_243_207_172_8,
+        1.054_318_231_187_074,
+        1.065_556_599_652_796,
+        -0.799_749_680_169_403_2,
+        0.385_013_355_409_706_9,
+        -2.078_616_693_017_569,
+        0.875_634_077_272_953_8,
+        -2.079_022_768_390_933,
+        1.596_435_439_942_262,
+        0.133_800_217_110_917_4,
+        1.242_891_021_876_471,
+    ];
+    const A: f64 = 0.014_289_763_856_964;
+    const B: f64 = 0.057_000_649_899_72;
+
+    let sa_r = sa * 1e-2;
+    let x = sa_r.sqrt();
+    let p_r = p_dbar * 1e-4;
+    let ct = C[0]
+        + sa_r * (C[1] + x * (C[2] + x * (C[3] + x * (C[4] + x * (C[5] + C[6] * x)))))
+        + p_r * (C[7] + p_r * (C[8] + C[9] * p_r))
+        + sa_r
+            * p_r
+            * (C[10]
+                + p_r * (C[12] + p_r * (C[15] + C[21] * sa_r))
+                + sa_r * (C[13] + C[17] * p_r + C[19] * sa_r)
+                + x * (C[11]
+                    + p_r * (C[14] + C[18] * p_r)
+                    + sa_r * (C[16] + C[20] * p_r + C[22] * sa_r)));
+    // Dissolved air depresses the freezing point slightly.
+    ct - saturation_fraction * 1e-3 * (2.4 - A * sa) * (1.0 + B * (1.0 - sa / GSW_SSO))
+}
+
+/// Thermal expansion coefficient α (1/K) with respect to CT.
+/// Returns `NaN` on `gsw` errors.
+pub fn alpha(sa: f64, ct: f64, p_dbar: f64) -> f64 {
+    finite(gsw_teos10::volume::alpha(sa, ct, p_dbar))
+}
+
+/// Saline contraction coefficient β (kg/g) at constant CT.
+/// Returns `NaN` on `gsw` errors.
+pub fn beta(sa: f64, ct: f64, p_dbar: f64) -> f64 {
+    finite(gsw_teos10::volume::beta(sa, ct, p_dbar))
+}
+
+/// Whether `alpha` and `beta` agree with central finite differences of `rho`:
+/// α ≈ −(1/ρ)·∂ρ/∂CT and β ≈ (1/ρ)·∂ρ/∂SA, each within the relative
+/// tolerance `tol`. A regression guard for the adapter; `false` whenever a
+/// value is non-finite. Under `mock-teos10`, `rho` is the mock while α/β are
+/// not, so the check fails there by design.
+pub fn check_thermo_consistency(sa: f64, ct: f64, p_dbar: f64, tol: f64) -> bool {
+    const H: f64 = 1e-3;
+    let rho0 = rho(sa, ct, p_dbar);
+    let alpha_fd = -(rho(sa, ct + H, p_dbar) - rho(sa, ct - H, p_dbar)) / (2.0 * H * rho0);
+    let beta_fd = (rho(sa + H, ct, p_dbar) - rho(sa - H, ct, p_dbar)) / (2.0 * H * rho0);
+    let close = |v: f64, fd: f64| (v - fd).abs() <= tol * v.abs();
+    close(alpha(sa, ct, p_dbar), alpha_fd) && close(beta(sa, ct, p_dbar), beta_fd)
+}
+
+/// Potential density anomaly σ0 (kg/m³) referenced to 0 dbar.
+/// Returns `NaN` on `gsw` errors.
+pub fn sigma0(sa: f64, ct: f64) -> f64 {
+    finite(gsw_teos10::volume::sigma0(sa, ct))
+}
+
+/// Isobaric specific heat capacity cp (J/(kg·K)) at in-situ temperature `t`.
+///
+/// `gsw` has no `cp_t_exact`, so cp = ∂h/∂t is taken by a central difference
+/// of the TEOS-10 enthalpy h(SA, CT(t), p); agrees with the Gibbs function
+/// to ~0.01 J/(kg·K). Returns `NaN` on `gsw` errors.
+pub fn specific_heat(sa: f64, t: f64, p_dbar: f64) -> f64 {
+    const DT: f64 = 1e-3;
+    let h = |t: f64| {
+        finite(gsw_teos10::volume::enthalpy(
+            sa,
+            ct_from_t(sa, t, p_dbar),
+            p_dbar,
+        ))
+    };
+    (h(t + DT) - h(t - DT)) / (2.0 * DT)
+}
+
+/// Keep a finite `gsw` result; errors and NaN/inf become `NaN`.
+fn finite<E>(r: Result<f64, E>) -> f64 {
+    r.ok().filter(|v| v.is_finite()).unwrap_or(f64::NAN)
+}
+
+/// Keep a strictly positive, finite `gsw` result; errors and the NaN/inf/0
+/// values its polynomials yield far outside their domain become `None`.
+fn positive_finite<E>(r: Result<f64, E>) -> Option<f64> {
+    r.ok().filter(|v| v.is_finite() && *v > 0.0)
+}
+
 /// In-situ density ρ from SA, CT and p (TEOS-10, 75-term polynomial).
-/// Returns `NaN` if the `gsw` library reports an error.
+/// Returns `NaN` outside the domain (see `try_rho`).
 pub fn rho(sa: f64, ct: f64, p_dbar: f64) -> f64 {
-    gsw_teos10::volume::rho(sa, ct, p_dbar).unwrap_or(f64::NAN)
+    try_rho(sa, ct, p_dbar).unwrap_or(f64::NAN)
+}
+
+/// Checked `rho`: `None` when `gsw` reports an error or returns a
+/// non-finite or non-positive density (it yields NaN for infinite inputs
+/// and 0.0 for absurd pressures rather than an error).
+///
+/// With `mock-teos10`, evaluates the linear mock EOS instead (see
+/// `mock_teos10`; not physical).
+pub fn try_rho(sa: f64, ct: f64, p_dbar: f64) -> Option<f64> {
+    #[cfg(feature = "mock-teos10")]
+    return crate::adapters::mock_teos10::try_rho(sa, ct, p_dbar);
+    #[cfg(not(feature = "mock-teos10"))]
+    positive_finite(gsw_teos10::volume::rho(sa, ct, p_dbar))
+}
+
+/// Isentropic compressibility κ (1/Pa) from SA, CT and p (TEOS-10).
+/// Returns `NaN` on `gsw` errors and non-finite or non-positive results.
+pub fn kappa(sa: f64, ct: f64, p_dbar: f64) -> f64 {
+    positive_finite(gsw_teos10::volume::kappa(sa, ct, p_dbar)).unwrap_or(f64::NAN)
+}
+
+/// Speed of sound (m/s) from SA, CT and p (TEOS-10).
+/// Returns `NaN` on `gsw` errors and non-finite or non-positive results.
+pub fn sound_speed(sa: f64, ct: f64, p_dbar: f64) -> f64 {
+    positive_finite(gsw_teos10::volume::sound_speed(sa, ct, p_dbar)).unwrap_or(f64::NAN)
 }
 
-#[cfg(all(test, not(feature = "approx_ct")))]
+#[cfg(all(test, not(feature = "mock-teos10")))]
 mod tests {
     use super::*;
+    use crate::adapters::manual_ct::pt0_from_t;
 
     #[test]
     fn pt0_iteration_converges_reasonably() {
@@ -59,11 +371,49 @@ mod tests {
     }
 
     #[test]
-    fn ct_identity_for_now() {
-        let sa = 35.0;
-        let t = 20.0;
-        let p = 10.0;
-        let ct = ct_from_t(sa, t, p);
-        assert!(ct <= t);
+    fn temperatures_below_t_min_are_out_of_domain() {
+        assert!(ct_from_t(35.0, T_MIN_C, 0.0).is_finite());
+        assert!(ct_from_t(35.0, -8.0, 0.0).is_nan());
+        assert!(pt_from_t(35.0, -8.0, 0.0).is_nan());
+        let cfg = Teos10Config::default();
+        assert!(ct_from_t_with_config(35.0, -8.0, 0.0, &cfg).is_nan());
+    }
+
+    #[test]
+    fn ct_freezing_matches_gibbs_equilibrium() {
+        // Air-free CT at which the chemical potential of water in seawater
+        // equals that of ice (full TEOS-10 Gibbs functions).
+        for (sa, p, ct) in [
+            (10.0, 0.0, -0.519_88),
+            (35.165_04, 0.0, -1.916_53),
+            (35.0, 1000.0, -2.691_00),
+            (100.0, 0.0, -6.349_89),
+        ] {
+            let got = ct_freezing(sa, p, 0.0);
+            assert!((got - ct).abs() < 6e-4, "SA {sa}, p {p}: {got} vs {ct}");
+        }
+        assert!(ct_freezing(35.0, 0.0, 1.0) < ct_freezing(35.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn non_finite_or_zero_gsw_density_is_a_domain_signal() {
+        // gsw returns Ok(NaN) for infinite SA and Ok(0.0) for absurd pressure.
+        assert!(rho(f64::INFINITY, 20.0, 0.0).is_nan());
+        assert!(rho(35.0, 20.0, 1e300).is_nan());
+        assert_eq!(try_rho(35.0, 20.0, 1e300), None);
+        assert!(sound_speed(35.0, 20.0, 1e300).is_nan());
+        assert!(try_rho(35.0, 20.0, 0.0).is_some());
+    }
+
+    #[test]
+    fn alpha_beta_match_density_finite_differences() {
+        for (sa, ct, p) in [
+            (35.165_04, 10.0, 0.0),
+            (35.0, 2.0, 2000.0),
+            (38.0, 28.0, 0.0),
+        ] {
+            assert!(check_thermo_consistency(sa, ct, p, 1e-5), "{sa} {ct} {p}");
+        }
+        assert!(!check_thermo_consistency(f64::NAN, 10.0, 0.0, 1e-5));
     }
 }
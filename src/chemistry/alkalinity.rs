@@ -0,0 +1,79 @@
+//! Pluggable carbonate-alkalinity speciation.
+//!
+//! The solver splits alkalinity into HCO3-, CO3^2- and OH-. The
+//! `AlkalinityModel` trait formalizes that step, mirroring
+//! `chloride::ChlorideEstimator`:
+//! - `FixedFraction`: the fixed `ALKA_FRAC_*` split (`alk_species_from_dkh`),
+//!   the solver default
+//! - `EquilibriumBased`: pH-driven split from the carbonate and water
+//!   dissociation constants, falling back to `FixedFraction` without a pH
+//!
+//! Every model returns species whose charge adds up to the alkalinity, so the
+//! charge balance and SP are unaffected; the split shows up in the expanded
+//! component tables (`Assumptions::expand_alk_species`).
+
+use super::{DKH_TO_MEQL, alk_species_from_dkh};
+
+/// A strategy for splitting alkalinity into carbonate-system species.
+pub trait AlkalinityModel {
+    /// Return `(n_hco3, n_co3, n_oh)` in mol/L for `alk_dkh` at temperature
+    /// `temp_c` (°C), salinity `sp` and, when known, the sample `ph`.
+    fn speciate(&self, alk_dkh: f64, temp_c: f64, sp: f64, ph: Option<f64>) -> (f64, f64, f64);
+}
+
+/// Fixed fractions `ALKA_FRAC_HCO3` / `ALKA_FRAC_CO3` / `ALKA_FRAC_OH` of the
+/// alkalinity (solver default).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixedFraction;
+
+/// Split from `ph` using K2 of carbonic acid (Lueker et al. 2000) and Kw
+/// (Millero 1995): OH- = Kw/[H+], and the remaining carbonate alkalinity is
+/// divided with CO3^2-/HCO3- = K2/[H+]. The constants are on the total scale
+/// and applied to the free-scale `Inputs::ph`; the offset shifts CO3^2- by a
+/// few percent at most.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EquilibriumBased;
+
+impl AlkalinityModel for FixedFraction {
+    fn speciate(&self, alk_dkh: f64, _temp_c: f64, _sp: f64, _ph: Option<f64>) -> (f64, f64, f64) {
+        let (n_hco3, n_co3, n_oh, _) = alk_species_from_dkh(alk_dkh, None);
+        (n_hco3, n_co3, n_oh)
+    }
+}
+
+impl AlkalinityModel for EquilibriumBased {
+    fn speciate(&self, alk_dkh: f64, temp_c: f64, sp: f64, ph: Option<f64>) -> (f64, f64, f64) {
+        let Some(ph) = ph else {
+            return FixedFraction.speciate(alk_dkh, temp_c, sp, None);
+        };
+        if alk_dkh <= 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let a_eq_l = alk_dkh * DKH_TO_MEQL / 1000.0;
+        let h = 10f64.powf(-ph);
+        let n_oh = (water_dissociation_constant(temp_c, sp) / h).min(a_eq_l);
+        let r = carbonate_k2(temp_c, sp) / h;
+        let n_hco3 = (a_eq_l - n_oh) / (1.0 + 2.0 * r);
+        (n_hco3, n_hco3 * r, n_oh)
+    }
+}
+
+/// Second dissociation constant of carbonic acid (mol/kg-SW, total scale)
+/// after Lueker et al. (2000), for temperature `t_c` (°C) and salinity `s`.
+pub fn carbonate_k2(t_c: f64, s: f64) -> f64 {
+    let s = s.max(0.0);
+    let t_k = t_c + 273.15;
+    let pk2 = 471.78 / t_k + 25.929 - 3.169_67 * t_k.ln() - 0.017_81 * s + 0.000_112_2 * s * s;
+    10f64.powf(-pk2)
+}
+
+/// Ion product of water (mol²/kg², total scale) after Millero (1995), for
+/// temperature `t_c` (°C) and salinity `s`.
+pub fn water_dissociation_constant(t_c: f64, s: f64) -> f64 {
+    let s = s.max(0.0);
+    let t_k = t_c + 273.15;
+    let ln_kw = 148.965_2 - 13_847.26 / t_k - 23.652_1 * t_k.ln()
+        + (118.67 / t_k - 5.977 + 1.049_5 * t_k.ln()) * s.sqrt()
+        - 0.016_15 * s;
+    ln_kw.exp()
+}
@@ -0,0 +1,101 @@
+//! Pluggable chloride estimation.
+//!
+//! The solver estimates chloride whenever `Inputs::cl` is missing. The
+//! `ChlorideEstimator` trait formalizes that step so callers can swap in their
+//! own model; the built-in strategies wrap the functions in the parent module:
+//! - `ChargeBalance`: electroneutrality only (`estimate_cl_mg_l_from_charge_balance`)
+//! - `RatioBased`: reference ion ratios only (`estimate_cl_mg_l_from_ratios`)
+//! - `RatioRobust`: weighted median of the ratio candidates
+//!   (`estimate_cl_mg_l_from_ratios_robust`)
+//! - `Blended`: adaptive blend of both (`estimate_cl_mg_l`), the solver default
+
+use super::{
+    estimate_cl_mg_l, estimate_cl_mg_l_from_charge_balance, estimate_cl_mg_l_from_ratios,
+    estimate_cl_mg_l_from_ratios_robust,
+};
+use crate::models::{Assumptions, Inputs};
+
+/// Auxiliary quantities available to a chloride estimator.
+///
+/// Species amounts are mol/L as returned by the speciation helpers
+/// (`boron_partition`, `alk_species_from_dkh`).
+#[derive(Clone, Copy, Debug)]
+pub struct EstimationContext {
+    /// Fallback fluoride (mg/L) when `Inputs::f` is missing.
+    pub default_f_mg_l: f64,
+    pub n_borate: f64,
+    pub n_hco3: f64,
+    pub n_co3: f64,
+    pub n_oh: f64,
+    /// Temperature (°C) at which HF dissociation is evaluated when
+    /// `Inputs::ph` is given; the solver passes `Assumptions::temp`.
+    pub temp_c: f64,
+    /// Salinity at which HF dissociation is evaluated; the solver passes
+    /// `Assumptions::salinity_norm`, as chloride is estimated before SP is
+    /// known.
+    pub salinity: f64,
+}
+
+impl Default for EstimationContext {
+    /// No speciated species or default fluoride, at the default
+    /// `Assumptions::temp` and `Assumptions::salinity_norm`.
+    fn default() -> Self {
+        let ass = Assumptions::default();
+        Self {
+            default_f_mg_l: 0.0,
+            n_borate: 0.0,
+            n_hco3: 0.0,
+            n_co3: 0.0,
+            n_oh: 0.0,
+            temp_c: ass.temp,
+            salinity: ass.salinity_norm,
+        }
+    }
+}
+
+/// A strategy for estimating chloride (mg/L) from an ion panel.
+pub trait ChlorideEstimator {
+    /// Return the estimated chloride concentration in mg/L (non-negative).
+    fn estimate(&self, inp: &Inputs, ctx: &EstimationContext) -> f64;
+}
+
+/// Chloride from electroneutrality alone.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChargeBalance;
+
+/// Chloride from reference molar ratios of the measured ions alone.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RatioBased;
+
+/// Chloride from the weighted median of the reference-ratio candidates,
+/// insensitive to a single outlying ion.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RatioRobust;
+
+/// Adaptive blend of charge balance and ratio estimates (solver default).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Blended;
+
+impl ChlorideEstimator for ChargeBalance {
+    fn estimate(&self, inp: &Inputs, ctx: &EstimationContext) -> f64 {
+        estimate_cl_mg_l_from_charge_balance(inp, ctx)
+    }
+}
+
+impl ChlorideEstimator for RatioBased {
+    fn estimate(&self, inp: &Inputs, _ctx: &EstimationContext) -> f64 {
+        estimate_cl_mg_l_from_ratios(inp)
+    }
+}
+
+impl ChlorideEstimator for RatioRobust {
+    fn estimate(&self, inp: &Inputs, _ctx: &EstimationContext) -> f64 {
+        estimate_cl_mg_l_from_ratios_robust(inp)
+    }
+}
+
+impl ChlorideEstimator for Blended {
+    fn estimate(&self, inp: &Inputs, ctx: &EstimationContext) -> f64 {
+        estimate_cl_mg_l(inp, ctx)
+    }
+}
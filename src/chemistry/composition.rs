@@ -0,0 +1,205 @@
+//! User-adjustable reference composition and boron parameters.
+//!
+//! `Default` reproduces the crate constants (`REF_MMOL_*`, `M_*`,
+//! `BORATE_FRACTION_DEFAULT`). Custom values must pass `validated()`, which
+//! rejects non-positive molar masses, negative reference amounts and
+//! fractions outside `[0, 1]`; without it a zero molar mass would silently be
+//! replaced by `TINY` in `mol_per_l` and yield absurd amounts.
+
+use super::*;
+use core::fmt;
+
+/// Validation failure for a custom composition or boron parameter set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompositionError {
+    /// A molar mass was zero, negative or non-finite.
+    NonPositiveMolarMass { species: &'static str, value: f64 },
+    /// A reference amount (mmol/kg) was negative or non-finite.
+    InvalidAmount { species: &'static str, value: f64 },
+    /// A fraction was outside `[0, 1]` or non-finite.
+    FractionOutOfRange { name: &'static str, value: f64 },
+}
+
+impl fmt::Display for CompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonPositiveMolarMass { species, value } => {
+                write!(f, "molar mass of {species} must be positive, got {value}")
+            }
+            Self::InvalidAmount { species, value } => {
+                write!(f, "reference amount of {species} must be >= 0, got {value}")
+            }
+            Self::FractionOutOfRange { name, value } => {
+                write!(f, "{name} must be within [0, 1], got {value}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompositionError {}
+
+fn check_mass(species: &'static str, value: f64) -> Result<(), CompositionError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(CompositionError::NonPositiveMolarMass { species, value })
+    }
+}
+
+fn check_amount(species: &'static str, value: f64) -> Result<(), CompositionError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(CompositionError::InvalidAmount { species, value })
+    }
+}
+
+fn check_fraction(name: &'static str, value: f64) -> Result<(), CompositionError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(CompositionError::FractionOutOfRange { name, value })
+    }
+}
+
+/// Reference seawater composition: amounts (mmol/kg at SP = 35) and molar
+/// masses (g/mol) of the major constituents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferenceComposition {
+    pub mmol_cl: f64,
+    pub mmol_na: f64,
+    pub mmol_so4: f64,
+    pub mmol_mg: f64,
+    pub mmol_ca: f64,
+    pub mmol_k: f64,
+    pub mmol_br: f64,
+    pub mmol_sr: f64,
+    pub mmol_f: f64,
+    pub mmol_b: f64,
+    pub m_cl: f64,
+    pub m_na: f64,
+    pub m_so4: f64,
+    pub m_mg: f64,
+    pub m_ca: f64,
+    pub m_k: f64,
+    pub m_br: f64,
+    pub m_sr: f64,
+    pub m_f: f64,
+    pub m_b: f64,
+}
+
+impl Default for ReferenceComposition {
+    fn default() -> Self {
+        Self {
+            mmol_cl: REF_MMOL_CL,
+            mmol_na: REF_MMOL_NA,
+            mmol_so4: REF_MMOL_SO4,
+            mmol_mg: REF_MMOL_MG,
+            mmol_ca: REF_MMOL_CA,
+            mmol_k: REF_MMOL_K,
+            mmol_br: REF_MMOL_BR,
+            mmol_sr: REF_MMOL_SR,
+            mmol_f: REF_MMOL_F,
+            mmol_b: REF_MMOL_B,
+            m_cl: M_CL,
+            m_na: M_NA,
+            m_so4: M_SO4,
+            m_mg: M_MG,
+            m_ca: M_CA,
+            m_k: M_K,
+            m_br: M_BR,
+            m_sr: M_SR,
+            m_f: M_F,
+            m_b: M_B,
+        }
+    }
+}
+
+impl ReferenceComposition {
+    /// `(name, mmol/kg, g/mol)` for each constituent, in summation order.
+    pub fn terms(&self) -> [(&'static str, f64, f64); 10] {
+        [
+            ("Cl-", self.mmol_cl, self.m_cl),
+            ("Na+", self.mmol_na, self.m_na),
+            ("SO4^2-", self.mmol_so4, self.m_so4),
+            ("Mg2+", self.mmol_mg, self.m_mg),
+            ("Ca2+", self.mmol_ca, self.m_ca),
+            ("K+", self.mmol_k, self.m_k),
+            ("Br-", self.mmol_br, self.m_br),
+            ("Sr2+", self.mmol_sr, self.m_sr),
+            ("F-", self.mmol_f, self.m_f),
+            ("B", self.mmol_b, self.m_b),
+        ]
+    }
+
+    /// Check every molar mass is positive and every amount non-negative.
+    pub fn validate(&self) -> Result<(), CompositionError> {
+        self.terms().iter().try_for_each(|&(name, mmol, m)| {
+            check_mass(name, m)?;
+            check_amount(name, mmol)
+        })
+    }
+
+    /// Validate and return `self`; the intended way to finish constructing a
+    /// custom composition (`ReferenceComposition { m_cl: .., ..Default::default() }.validated()`).
+    pub fn validated(self) -> Result<Self, CompositionError> {
+        self.validate().map(|_| self)
+    }
+
+    /// Total reference mass in g/kg (see `sum_ref_gkg`).
+    pub fn sum_gkg(&self) -> f64 {
+        self.terms()
+            .iter()
+            .map(|(_, mmol, m)| (mmol * m) / 1000.0)
+            .sum()
+    }
+}
+
+/// Boron speciation parameters: molar masses (g/mol) and borate fraction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoronParams {
+    pub m_b: f64,
+    pub m_boric: f64,
+    pub m_borate: f64,
+    pub borate_fraction: f64,
+}
+
+impl Default for BoronParams {
+    fn default() -> Self {
+        Self {
+            m_b: M_B,
+            m_boric: M_BORIC,
+            m_borate: M_BORATE,
+            borate_fraction: BORATE_FRACTION_DEFAULT,
+        }
+    }
+}
+
+impl BoronParams {
+    /// Check molar masses are positive and `borate_fraction` is within `[0, 1]`.
+    pub fn validate(&self) -> Result<(), CompositionError> {
+        check_mass("B", self.m_b)?;
+        check_mass("B(OH)3", self.m_boric)?;
+        check_mass("B(OH)4-", self.m_borate)?;
+        check_fraction("borate_fraction", self.borate_fraction)
+    }
+
+    /// Validate and return `self`.
+    pub fn validated(self) -> Result<Self, CompositionError> {
+        self.validate().map(|_| self)
+    }
+
+    /// Partition total boron (mg/L elemental B) into `(n_boric, n_borate)` mol/L.
+    /// The borate fraction is clamped to `[0, 1]`; non-positive input yields zeros.
+    pub fn partition(&self, b_mg_l: f64) -> (f64, f64) {
+        if b_mg_l <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let n_b_total = mol_per_l(b_mg_l, self.m_b);
+        let alpha = self.borate_fraction.clamp(0.0, 1.0);
+        let n_borate = alpha * n_b_total;
+        let n_boric = n_b_total - n_borate;
+        (n_boric, n_borate)
+    }
+}
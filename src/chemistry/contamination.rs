@@ -0,0 +1,86 @@
+//! QC heuristic: recognize major-ion excesses that point at a specific salt.
+//!
+//! Each major ion implies a scaling `n_i / REF_MMOL_i` of the reference
+//! composition. The median scaling (`median_reference_scale`) is taken as the
+//! panel's seawater
+//! baseline, which a one- or two-ion spike cannot move; whatever an ion
+//! carries above that baseline is its excess. A salt is suggested when all of
+//! its ions are in excess by at least `CONTAMINATION_MIN_EXCESS` and the
+//! excesses match its stoichiometry.
+
+use super::*;
+use serde::Serialize;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Smallest relative excess over the baseline (0.1 = 10 %) that counts.
+pub const CONTAMINATION_MIN_EXCESS: f64 = 0.1;
+/// Relative excess at which the magnitude part of the confidence saturates.
+pub const CONTAMINATION_FULL_EXCESS: f64 = 0.5;
+
+/// A salt whose addition would explain part of the panel's ion excesses.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ContaminationHint {
+    /// Common name of the suspected salt, e.g. `"gypsum"`.
+    pub salt: &'static str,
+    /// 0..1: how well the excesses match the salt's stoichiometry, scaled
+    /// down for excesses below `CONTAMINATION_FULL_EXCESS`.
+    pub confidence: f64,
+}
+
+/// Suspected salts, strongest first; empty for a seawater-like panel.
+///
+/// Recognizes gypsum (excess Ca + SO4, 1:1), halite (excess Na + Cl, 1:1,
+/// only when Cl is measured) and a potassium salt (excess K). Concentrations
+/// are read as given; the ratios do not depend on the mass unit.
+pub fn diagnose_contamination(inp: &Inputs) -> Vec<ContaminationHint> {
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+    let cl_mg_l = inp.cl.filter(|&c| c > 0.0);
+    let scale = |mg_l: f64, molar_mass: f64, ref_mmol: f64| mol_per_l(mg_l, molar_mass) / ref_mmol;
+    let na = scale(inp.na, M_NA, REF_MMOL_NA);
+    let ca = scale(inp.ca, M_CA, REF_MMOL_CA);
+    let k = scale(inp.k, M_K, REF_MMOL_K);
+    let so4 = scale(so4_mg_l, M_SO4, REF_MMOL_SO4);
+    let cl = cl_mg_l.map(|c| scale(c, M_CL, REF_MMOL_CL));
+
+    let baseline = median_reference_scale(inp);
+    let mut hints = Vec::new();
+    if baseline.is_nan() || baseline <= 0.0 {
+        return hints;
+    }
+
+    // Relative excess over the baseline and absolute excess (mmol/L).
+    let excess = |s: f64, ref_mmol: f64| (s / baseline - 1.0, (s - baseline) * ref_mmol);
+    let mut suggest = |salt: &'static str, ions: &[(f64, f64)]| {
+        if ions.iter().any(|&(rel, _)| rel < CONTAMINATION_MIN_EXCESS) {
+            return;
+        }
+        let (lo, hi) = ions
+            .iter()
+            .fold((f64::INFINITY, 0.0f64), |(lo, hi), &(_, abs)| {
+                (lo.min(abs), hi.max(abs))
+            });
+        let weakest = ions
+            .iter()
+            .map(|&(rel, _)| rel)
+            .fold(f64::INFINITY, f64::min);
+        let confidence = (lo / hi) * (weakest / CONTAMINATION_FULL_EXCESS).min(1.0);
+        hints.push(ContaminationHint { salt, confidence });
+    };
+
+    suggest(
+        "gypsum",
+        &[excess(ca, REF_MMOL_CA), excess(so4, REF_MMOL_SO4)],
+    );
+    if let Some(cl) = cl {
+        suggest(
+            "halite",
+            &[excess(na, REF_MMOL_NA), excess(cl, REF_MMOL_CL)],
+        );
+    }
+    suggest("potassium salt", &[excess(k, REF_MMOL_K)]);
+
+    hints.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    hints
+}
@@ -0,0 +1,24 @@
+//! Fluoride speciation (HF ⇌ H+ + F-).
+//!
+//! In seawater HF is a fairly strong acid (pKF ≈ 2.6 on the free scale at
+//! 25 °C, S = 35), so F- dominates above pH ~4 and HF⁰ only matters in acidic
+//! or hydrothermal samples. HF carries no charge, so the undissociated part is
+//! dropped from the charge balance while its mass still counts.
+
+/// Dissociation constant of HF (mol/kg-SW, free H+ scale) after Dickson &
+/// Riley (1979), for temperature `t_c` (°C) and salinity `s`.
+pub fn hf_dissociation_constant(t_c: f64, s: f64) -> f64 {
+    let s = s.max(0.0);
+    let t_k = t_c + 273.15;
+    let ionic_strength = 19.924 * s / (1000.0 - 1.005 * s);
+    let ln_kf = 1590.2 / t_k - 12.641 + 1.525 * ionic_strength.sqrt();
+    ln_kf.exp() * (1.0 - 0.001_005 * s)
+}
+
+/// Fraction (0..1) of total fluoride present as F- at free-scale `ph`,
+/// temperature `t_c` (°C) and salinity `s`: `KF / (KF + [H+])`.
+pub fn fluoride_anion_fraction(ph: f64, t_c: f64, s: f64) -> f64 {
+    let kf = hf_dissociation_constant(t_c, s);
+    let h = 10f64.powf(-ph);
+    kf / (kf + h)
+}
@@ -31,12 +31,14 @@
 //! # Examples
 //! ```rust
 //! use salinity_rs::chemistry::{alk_species_from_dkh, estimate_cl_mg_l, boron_partition, BORATE_FRACTION_DEFAULT};
+//! use salinity_rs::chemistry::chloride::EstimationContext;
 //! use salinity_rs::models::Inputs;
 //!
 //! let (n_hco3, n_co3, n_oh, _alk_mass) = alk_species_from_dkh(8.0, None);
 //! let (_n_boric, n_borate) = boron_partition(4.0, BORATE_FRACTION_DEFAULT);
-//! let inputs = Inputs { na: 10780.0, mg: 1290.0, ca: 430.0, k: 380.0, sr: 8.0, br: 65.0, cl: None, f: None, s: 910.0, b: 4.0, alk_dkh: Some(8.0) };
-//! let cl_mg_l = estimate_cl_mg_l(&inputs, 1.3, n_borate, n_hco3, n_co3, n_oh);
+//! let inputs = Inputs { na: 10780.0, mg: 1290.0, ca: 430.0, k: 380.0, sr: 8.0, br: 65.0, cl: None, f: None, s: 910.0, b: 4.0, alk_dkh: Some(8.0), ..Default::default() };
+//! let ctx = EstimationContext { default_f_mg_l: 1.3, n_borate, n_hco3, n_co3, n_oh, ..Default::default() };
+//! let cl_mg_l = estimate_cl_mg_l(&inputs, &ctx);
 //! assert!(cl_mg_l > 0.0);
 //! ```
 //!
@@ -54,10 +56,27 @@
 //! - CO2SYS approaches for dynamic carbonate chemistry
 //!
 //! This documentation supplements inline comments below.
+
+pub mod alkalinity;
+pub mod chloride;
+pub mod composition;
+pub mod contamination;
+pub mod fluoride;
+pub mod nutrients;
+pub mod units;
+
+use units::{MgPerL, MolPerL};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
 /// Reference salinity (Practical Salinity Scale TEOS-10) used for baseline ratios.
 pub const SR_REF: f64 = 35.16504;
 /// Default reference alkalinity expressed in dKH (German degrees of carbonate hardness).
 pub const DEFAULT_REF_ALK_DKH: f64 = 8.0;
+/// Default `Assumptions::alk_ceiling_dkh`: well above any natural or reef-tank
+/// alkalinity (seawater ~6–12 dKH), so exceeding it points at a typo.
+pub const ALK_CEILING_DKH_DEFAULT: f64 = 50.0;
 
 /// Molar / mass constants (g/mol) for elemental ions and species.
 /// Values sourced from standard atomic weights; underscores improve readability.
@@ -74,6 +93,94 @@ pub const M_SO4: f64 = 96.06;
 pub const M_B: f64 = 10.81;
 pub const M_BORIC: f64 = 61.83; // B(OH)3
 pub const M_BORATE: f64 = 60.83; // B(OH)4-
+pub const M_HCO3: f64 = 61.017;
+pub const M_CO3: f64 = 60.009;
+pub const M_OH: f64 = 17.007;
+
+/// Canonical display names and molar masses (g/mol) of the dissolved species
+/// the crate reports, in component-table order. Alkalinity (`Alk.`) is a
+/// lumped quantity and has no entry; its carbonate-system species appear
+/// only with `Assumptions::expand_alk_species`.
+const KNOWN_SPECIES: [(&str, f64); 14] = [
+    ("Na+", M_NA),
+    ("Ca2+", M_CA),
+    ("Mg2+", M_MG),
+    ("K+", M_K),
+    ("Sr2+", M_SR),
+    ("Br-", M_BR),
+    ("SO4^2-", M_SO4),
+    ("F-", M_F),
+    ("HCO3-", M_HCO3),
+    ("CO3^2-", M_CO3),
+    ("OH-", M_OH),
+    ("B(OH)3", M_BORIC),
+    ("B(OH)4-", M_BORATE),
+    ("Cl-", M_CL),
+];
+
+/// All known species as `(display name, molar mass g/mol)`, in
+/// component-table order (without `Alk.`).
+pub fn known_species() -> &'static [(&'static str, f64)] {
+    &KNOWN_SPECIES
+}
+
+/// Canonical `'static` form of a component-table name (a known species,
+/// `Alk.` or the lumped `Custom` row), e.g. to rebuild `&'static str` keys
+/// from deserialized strings.
+pub fn species_name(name: &str) -> Option<&'static str> {
+    match name {
+        "Alk." => return Some("Alk."),
+        "Custom" => return Some("Custom"),
+        _ => {}
+    }
+    KNOWN_SPECIES
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|&(known, _)| known)
+}
+
+/// Molar mass (g/mol) of a species by display name, if known.
+pub fn molar_mass(species: &str) -> Option<f64> {
+    KNOWN_SPECIES
+        .iter()
+        .find(|(name, _)| *name == species)
+        .map(|&(_, m)| m)
+}
+
+/// A single measurable ion of an `Inputs` panel, e.g. the anchor of
+/// `reconstruct_panel`. Sulfate is read through `sulfate_mg_l` (SO4 basis),
+/// boron on the elemental basis of `Inputs::b`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Species {
+    Na,
+    Ca,
+    Mg,
+    K,
+    Sr,
+    Br,
+    So4,
+    F,
+    B,
+    Cl,
+}
+
+impl Species {
+    /// Concentration of this species in `inp` (mg/L); missing Cl/F read as 0.
+    pub fn mg_l(self, inp: &Inputs) -> f64 {
+        match self {
+            Species::Na => inp.na,
+            Species::Ca => inp.ca,
+            Species::Mg => inp.mg,
+            Species::K => inp.k,
+            Species::Sr => inp.sr,
+            Species::Br => inp.br,
+            Species::So4 => sulfate_mg_l(inp).0,
+            Species::F => inp.f.unwrap_or(0.0),
+            Species::B => inp.b,
+            Species::Cl => inp.cl.unwrap_or(0.0),
+        }
+    }
+}
 
 // Reference mmol/kg (standard seawater)
 /// Reference mmol/kg of chloride (Cl-) in standard seawater.
@@ -108,6 +215,8 @@ pub const ALKA_FRAC_OH: f64 = 0.01;
 pub const BORATE_FRACTION_DEFAULT: f64 = 0.20;
 /// Conversion factor: dKH to meq/L of alkalinity.
 pub const DKH_TO_MEQL: f64 = 0.357; // dKH -> meq/L
+/// Conversion factor: dGH to mmol/L of Ca + Mg (1 dGH = 10 mg/L CaO).
+pub const DGH_TO_MMOL_L: f64 = 0.178_3; // dGH -> mmol/L
 /// mg per meq as CaCO3 equivalent for alkalinity mass representation.
 pub const MG_PER_MEQ_AS_CACO3: f64 = 50.043; // mg/meq as CaCO3
 
@@ -120,23 +229,14 @@ pub const RATIO_BLEND_ALPHA: f64 = 0.6;
 pub const RATIO_BLEND_THRESHOLD: f64 = 0.8;
 
 use crate::models::Inputs;
+use crate::warning::Warning;
+use chloride::EstimationContext;
+use serde::{Deserialize, Serialize};
 
 /// Sum of reference ion masses (g/kg) based on `REF_MMOL_*` (excludes boron species & alkalinity).
 /// Returns total g/kg of major ions under standard seawater composition.
 pub fn sum_ref_gkg() -> f64 {
-    let terms = [
-        (REF_MMOL_CL, M_CL),
-        (REF_MMOL_NA, M_NA),
-        (REF_MMOL_SO4, M_SO4),
-        (REF_MMOL_MG, M_MG),
-        (REF_MMOL_CA, M_CA),
-        (REF_MMOL_K, M_K),
-        (REF_MMOL_BR, M_BR),
-        (REF_MMOL_SR, M_SR),
-        (REF_MMOL_F, M_F),
-        (REF_MMOL_B, M_B),
-    ];
-    terms.iter().map(|(mmol, m)| (mmol * m) / 1000.0).sum()
+    composition::ReferenceComposition::default().sum_gkg()
 }
 
 /// Convert mass concentration (mg/L) to molar concentration (mol/L).
@@ -145,16 +245,48 @@ pub fn sum_ref_gkg() -> f64 {
 /// - `mg_l`: mass concentration (mg per liter), negative values treated as 0.
 /// - `molar_mass_g_mol`: molar mass (g/mol); guarded against division by ~0 via `TINY`.
 ///
-/// Returns mol/L (not mmol/L!). For mmol/L multiply by 1000.
+/// Returns mol/L (not mmol/L!). For mmol/L multiply by 1000. `f64` wrapper
+/// around `units::MgPerL::to_mol_per_l`.
 pub fn mol_per_l(mg_l: f64, molar_mass_g_mol: f64) -> f64 {
-    (mg_l.max(0.0)) / 1000.0 / molar_mass_g_mol.max(TINY)
+    MgPerL(mg_l).to_mol_per_l(molar_mass_g_mol).0
+}
+
+/// Convert alkalinity given as ppm (mg/L) CaCO3 to dKH.
+///
+/// Uses `MG_PER_MEQ_AS_CACO3` (mg/meq) and `DKH_TO_MEQL` (meq/L per dKH), so
+/// that ~143 ppm CaCO3 corresponds to 8 dKH. Negative values are clamped to 0.
+pub fn dkh_from_ppm_caco3(ppm_caco3: f64) -> f64 {
+    ppm_caco3.max(0.0) / MG_PER_MEQ_AS_CACO3 / DKH_TO_MEQL
+}
+
+/// Total alkalinity (meq/L) of `inp`: the measured alkalinity (or
+/// `assumed_alk_dkh` when none is measured) as carbonate-system alkalinity,
+/// plus silicate and phosphate alkalinity when `Inputs::ph` is given
+/// (`nutrients::nutrient_alkalinity_meq_l`).
+pub fn total_alkalinity_meq_l(inp: &Inputs, assumed_alk_dkh: Option<f64>) -> f64 {
+    let carbonate = inp.measured_alk_dkh().or(assumed_alk_dkh).unwrap_or(0.0);
+    carbonate.max(0.0) * DKH_TO_MEQL + nutrients::nutrient_alkalinity_meq_l(inp)
+}
+
+/// mg per meq used to turn alkalinity into mass: `mg_per_meq` when finite
+/// and positive, otherwise `MG_PER_MEQ_AS_CACO3`.
+///
+/// The measured alkalinity mass and the reference alkalinity mass in
+/// `ref_sum_with_boron_species_and_ref_alk` both go through this, so the two
+/// always use the same convention and an override cancels out in the
+/// salinity ratio.
+pub fn effective_alk_mg_per_meq(mg_per_meq: Option<f64>) -> f64 {
+    mg_per_meq
+        .filter(|m| m.is_finite() && *m > 0.0)
+        .unwrap_or(MG_PER_MEQ_AS_CACO3)
 }
 
 /// Partition alkalinity expressed in dKH into carbonate system species.
 ///
 /// Inputs:
 /// - `alk_dkh`: total alkalinity in dKH; <= 0 returns zeros.
-/// - `mg_per_meq`: optional mg per meq conversion (defaults to CaCO3 equivalence).
+/// - `mg_per_meq`: optional mg per meq conversion (see `effective_alk_mg_per_meq`;
+///   defaults to CaCO3 equivalence).
 ///
 /// Returns tuple `(n_hco3, n_co3, n_oh, alkalinity_mass_mg_l)` where n_* are mol/L.
 /// Hydroxide is included as a small fixed fraction. Carbonate (CO3^2-) moles are half its eq.
@@ -173,8 +305,7 @@ pub fn alk_species_from_dkh(alk_dkh: f64, mg_per_meq: Option<f64>) -> (f64, f64,
     let n_co3 = a_co3 / 2.0;
     let n_oh = a_oh / 1.0;
 
-    let mg_per_meq_eff = mg_per_meq.unwrap_or(MG_PER_MEQ_AS_CACO3);
-    let alk_mass_mg_l = a_meq_l * mg_per_meq_eff;
+    let alk_mass_mg_l = a_meq_l * effective_alk_mg_per_meq(mg_per_meq);
     (n_hco3, n_co3, n_oh, alk_mass_mg_l)
 }
 
@@ -186,143 +317,465 @@ pub fn alk_species_from_dkh(alk_dkh: f64, mg_per_meq: Option<f64>) -> (f64, f64,
 ///
 /// Returns `(n_boric, n_borate)` in mol/L. Negative or zero input yields zeros.
 pub fn boron_partition(b_mg_l: f64, borate_fraction: f64) -> (f64, f64) {
-    if b_mg_l <= 0.0 {
-        return (0.0, 0.0);
+    composition::BoronParams {
+        borate_fraction,
+        ..Default::default()
+    }
+    .partition(b_mg_l)
+}
+
+/// How the sulfate concentration was obtained from `Inputs`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SulfateSource {
+    /// Converted from elemental sulfur `s` via `s * M_SO4 / M_S`.
+    FromSulfur,
+    /// Taken directly from `so4`.
+    Sulfate,
+}
+
+/// Resolve the sulfate concentration (mg/L as SO4) used throughout the crate.
+///
+/// `Inputs::so4` wins when present; otherwise elemental S (`Inputs::s`) is
+/// converted by molar mass ratio. Note that passing sulfate in the `s` field
+/// inflates SO4 by a factor of ~3 (M_SO4 / M_S).
+pub fn sulfate_mg_l(inp: &Inputs) -> (f64, SulfateSource) {
+    match inp.so4 {
+        Some(so4) => (so4, SulfateSource::Sulfate),
+        None => ((inp.s / M_S) * M_SO4, SulfateSource::FromSulfur),
     }
-    let n_b_total = mol_per_l(b_mg_l, M_B);
-    let alpha = borate_fraction.clamp(0.0, 1.0);
-    let n_borate = alpha * n_b_total;
-    let n_boric = n_b_total - n_borate;
-    (n_boric, n_borate)
+}
+
+/// Relative difference between `so4` and SO4 converted from `s` above which
+/// `reconcile_sulfate` reports the two as conflicting.
+pub const SULFATE_CONFLICT_TOLERANCE: f64 = 0.05;
+
+/// Sulfate (mg/L as SO4) from `sulfate_mg_l`, plus a
+/// `Warning::SulfateConflict` when both `s` and `so4` are reported and
+/// disagree by more than `SULFATE_CONFLICT_TOLERANCE`.
+///
+/// The precedence is that of `sulfate_mg_l`: `so4` wins and `s` is ignored,
+/// so the two are never added. A conflict usually means sulfate was entered
+/// in the `s` field, or the panel mixes two analyses.
+pub fn reconcile_sulfate(inp: &Inputs) -> (f64, Option<Warning>) {
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+    let warning = inp.so4.filter(|_| inp.s > 0.0).and_then(|so4| {
+        let from_s = (inp.s / M_S) * M_SO4;
+        let rel = (so4 - from_s).abs() / so4.abs().max(from_s);
+        (rel > SULFATE_CONFLICT_TOLERANCE).then_some(Warning::SulfateConflict {
+            so4_mg_l: so4,
+            so4_from_s_mg_l: from_s,
+        })
+    });
+    (so4_mg_l, warning)
+}
+
+/// Charge equivalents (eq/L) of each ion in a panel, see `charge_breakdown`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ChargeBreakdown {
+    /// Na+, Mg2+, Ca2+, K+, Sr2+, and `Custom+` (positively charged
+    /// `Inputs::custom` ions).
+    pub cations: [(&'static str, f64); 6],
+    /// Cl-, SO4^2-, Br-, F-, B(OH)4-, HCO3-, CO3^2-, OH-, `Si+P alk.`
+    /// (silicate and phosphate alkalinity, zero without `Inputs::ph`), and
+    /// `Custom-` (negatively charged `Inputs::custom` ions).
+    pub anions: [(&'static str, f64); 10],
+    pub total_cations: f64,
+    pub total_anions: f64,
+    /// `total_cations - total_anions`; positive means missing anions.
+    pub residual: f64,
+}
+
+/// Fraction of fluoride carrying charge: 1 without `Inputs::ph`, otherwise the
+/// F- share at temperature `t_c` (°C) and salinity `s` (the rest is HF⁰).
+fn fluoride_charge_fraction(inp: &Inputs, t_c: f64, s: f64) -> f64 {
+    inp.ph
+        .map_or(1.0, |ph| fluoride::fluoride_anion_fraction(ph, t_c, s))
+}
+
+/// Fluoride (mg/L) of `inp`: `Inputs::f` when given, otherwise
+/// `default_f_mg_l` (the solver passes `Assumptions::default_f_mg_l`);
+/// negative values count as 0.
+///
+/// The single fluoride resolution shared by the charge balance (and thus
+/// `estimate_cl_mg_l`) and the solver's mass budget.
+pub fn fluoride_mg_l(inp: &Inputs, default_f_mg_l: f64) -> f64 {
+    inp.f.unwrap_or(default_f_mg_l).max(0.0)
+}
+
+/// Charge equivalents (eq/L, non-negative) of the `Inputs::custom` ions whose
+/// charge has the sign of `sign`.
+fn custom_equivalents(inp: &Inputs, sign: i32) -> f64 {
+    inp.custom_ions()
+        .filter(|ion| ion.charge.signum() == sign)
+        .map(|ion| f64::from(ion.charge.abs()) * mol_per_l(ion.mg_l, ion.molar_mass))
+        .sum()
+}
+
+/// Total mass (mg/L) of the `Inputs::custom` ions; negative values count as 0.
+pub fn custom_mass_mg_l(inp: &Inputs) -> f64 {
+    inp.custom_ions().map(|ion| ion.mg_l.max(0.0)).sum()
+}
+
+/// Itemize the charge balance of `inp` with chloride `cl_mg_l`.
+///
+/// Ions are mg/L as in `Inputs`; fluoride is `fluoride_mg_l` with
+/// `ctx.default_f_mg_l` (only its F- share at `ctx.temp_c` and
+/// `ctx.salinity` counts when `Inputs::ph` is given) and sulfate follows
+/// `sulfate_mg_l`. With `Inputs::ph`, silicate and phosphate alkalinity
+/// (`nutrients::nutrient_alkalinity_meq_l`) count as anions too, and
+/// `Inputs::custom` ions are summed by sign into `Custom+` / `Custom-`.
+/// Borate and the carbonate species come from `ctx`. Each entry is charge ×
+/// mol/L.
+pub fn charge_breakdown(inp: &Inputs, cl_mg_l: f64, ctx: &EstimationContext) -> ChargeBreakdown {
+    let cations = [
+        ("Na+", mol_per_l(inp.na, M_NA)),
+        ("Mg2+", 2.0 * mol_per_l(inp.mg, M_MG)),
+        ("Ca2+", 2.0 * mol_per_l(inp.ca, M_CA)),
+        ("K+", mol_per_l(inp.k, M_K)),
+        ("Sr2+", 2.0 * mol_per_l(inp.sr, M_SR)),
+        ("Custom+", custom_equivalents(inp, 1)),
+    ];
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+    let anions = [
+        ("Cl-", mol_per_l(cl_mg_l, M_CL)),
+        ("SO4^2-", 2.0 * mol_per_l(so4_mg_l, M_SO4)),
+        ("Br-", mol_per_l(inp.br, M_BR)),
+        (
+            "F-",
+            mol_per_l(fluoride_mg_l(inp, ctx.default_f_mg_l), M_F)
+                * fluoride_charge_fraction(inp, ctx.temp_c, ctx.salinity),
+        ),
+        ("B(OH)4-", ctx.n_borate),
+        ("HCO3-", ctx.n_hco3),
+        ("CO3^2-", 2.0 * ctx.n_co3),
+        ("OH-", ctx.n_oh),
+        (
+            "Si+P alk.",
+            nutrients::nutrient_alkalinity_meq_l(inp) / 1000.0,
+        ),
+        ("Custom-", custom_equivalents(inp, -1)),
+    ];
+    let total_cations = cations.iter().map(|(_, eq)| eq).sum::<f64>();
+    let total_anions = anions.iter().map(|(_, eq)| eq).sum::<f64>();
+    ChargeBreakdown {
+        cations,
+        anions,
+        total_cations,
+        total_anions,
+        residual: total_cations - total_anions,
+    }
+}
+
+/// Major-ion equivalents (meq/L) of `inp` with chloride `cl_mg_l`, the
+/// native unit of Stiff and Piper diagrams.
+///
+/// Cations (Na+, Mg2+, Ca2+, K+, Sr2+) come first, then Cl-, SO4^2- (per
+/// `sulfate_mg_l`) and Br-. Fluoride and `Alk.` (total alkalinity,
+/// `Inputs::measured_alk_dkh` × `DKH_TO_MEQL`) are appended only when
+/// measured; no defaults are filled in.
+pub fn ion_equivalents_meq_l(inp: &Inputs, cl_mg_l: f64) -> Vec<(&'static str, f64)> {
+    let meq_l =
+        |mg_l: f64, molar_mass: f64, charge: f64| 1000.0 * charge * mol_per_l(mg_l, molar_mass);
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+    let mut table = vec![
+        ("Na+", meq_l(inp.na, M_NA, 1.0)),
+        ("Mg2+", meq_l(inp.mg, M_MG, 2.0)),
+        ("Ca2+", meq_l(inp.ca, M_CA, 2.0)),
+        ("K+", meq_l(inp.k, M_K, 1.0)),
+        ("Sr2+", meq_l(inp.sr, M_SR, 2.0)),
+        ("Cl-", meq_l(cl_mg_l, M_CL, 1.0)),
+        ("SO4^2-", meq_l(so4_mg_l, M_SO4, 2.0)),
+        ("Br-", meq_l(inp.br, M_BR, 1.0)),
+    ];
+    if let Some(f) = inp.f {
+        table.push(("F-", meq_l(f, M_F, 1.0)));
+    }
+    if let Some(alk) = inp.measured_alk_dkh() {
+        table.push(("Alk.", alk.max(0.0) * DKH_TO_MEQL));
+    }
+    table
+}
+
+/// Net charge (eq/L, cations minus anions) of `inp` with chloride
+/// `cl_mg_l`; the aggregate of `charge_breakdown`.
+pub fn charge_balance_residual(inp: &Inputs, cl_mg_l: f64, ctx: &EstimationContext) -> f64 {
+    charge_breakdown(inp, cl_mg_l, ctx).residual
 }
 
 /// Estimate chloride concentration (mg/L) by charge balance excluding chloride.
 ///
 /// Inputs (from `Inputs`) are mg/L for ions except optional fluoride override.
-/// Additional species contributions: borate, bicarbonate, carbonate, hydroxide (from speciation functions).
+/// Additional species contributions: borate, bicarbonate, carbonate, hydroxide (from speciation functions in `ctx`).
 ///
 /// Returns chloride mg/L (non-negative). If negative balance occurs, clamps to 0.
-pub fn estimate_cl_mg_l_from_charge_balance(
-    inp: &Inputs,
-    default_f_mg_l: f64,
-    n_borate: f64,
-    n_hco3: f64,
-    n_co3: f64,
-    n_oh: f64,
-) -> f64 {
-    // Inputs are mg/L; mol_per_l expects mg/L directly.
-    let pos = 1.0 * mol_per_l(inp.na, M_NA)
-        + 2.0 * mol_per_l(inp.mg, M_MG)
-        + 2.0 * mol_per_l(inp.ca, M_CA)
-        + 1.0 * mol_per_l(inp.k, M_K)
-        + 2.0 * mol_per_l(inp.sr, M_SR);
-
-    // Convert S (mg/L as elemental S) to sulfate mg/L via molar mass ratio.
-    let so4_mg_l = (inp.s / M_S) * M_SO4;
-    let n_so4 = mol_per_l(so4_mg_l, M_SO4);
-    let mut neg = 2.0 * n_so4;
-    // Monovalent anions (mg/L)
-    neg += mol_per_l(inp.br, M_BR);
-    let f_mg_l = inp.f.unwrap_or(default_f_mg_l);
-    neg += mol_per_l(f_mg_l, M_F);
-
-    neg += 1.0 * n_borate;
-    neg += 1.0 * n_hco3 + 2.0 * n_co3 + 1.0 * n_oh;
-
-    let n_cl = pos - neg;
-    let mg_l_cl = (n_cl.max(0.0)) * M_CL * 1000.0;
+pub fn estimate_cl_mg_l_from_charge_balance(inp: &Inputs, ctx: &EstimationContext) -> f64 {
+    // Chloride balances whatever charge is left without it.
+    let n_cl = charge_balance_residual(inp, 0.0, ctx);
+    let MgPerL(mg_l_cl) = MolPerL(n_cl.max(0.0)).to_mg_per_l(M_CL);
+    mg_l_cl.max(MIN_CL_MG_L)
+}
+
+/// Estimate chloride (mg/L) from reference ion ratios alone.
+///
+/// Each measured species with a stable reference ratio yields a candidate
+///    n_cl_i = n_i / r_i, where r_i = REF_MMOL_i / REF_MMOL_CL (molar ratios).
+/// Candidates are averaged with weights proportional to the reference molar
+/// abundances (Na strongest influence). Species reported as zero/negative are
+/// ignored.
+///
+/// Returns chloride mg/L >= 0 (0 when no usable species is present).
+pub fn estimate_cl_mg_l_from_ratios(inp: &Inputs) -> f64 {
+    let MgPerL(mg_l_cl) = MolPerL(n_cl_from_ratios(inp)).to_mg_per_l(M_CL);
     mg_l_cl.max(MIN_CL_MG_L)
 }
 
-/// Estimate chloride (mg/L) using a blended strategy:
-/// 1) Primary: charge balance
-/// 2) Secondary: ion ratio constraints relative to reference seawater composition
+/// Estimate chloride (mg/L) from reference ion ratios, robust to outliers.
 ///
-/// The ratio-based estimate derives n_cl candidates from measured species via
-///    n_cl_i = n_i / r_i_ref, where r_i_ref = REF_MMOL_i / REF_MMOL_CL (molar ratios).
-/// We then compute a weighted average across available species and blend it with
-/// the charge-balance estimate. This improves robustness when Cl is missing.
+/// Uses the same per-species candidates and weights as
+/// `estimate_cl_mg_l_from_ratios` but takes their weighted median instead of
+/// the weighted mean, so a single grossly wrong ion cannot pull the estimate.
+///
+/// Returns chloride mg/L >= 0 (0 when no usable species is present).
+pub fn estimate_cl_mg_l_from_ratios_robust(inp: &Inputs) -> f64 {
+    let mut candidates: Vec<(f64, f64)> = ratio_cl_candidates(inp).into_iter().flatten().collect();
+    candidates.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let half = candidates.iter().map(|&(w, _)| w).sum::<f64>() / 2.0;
+    let mut cumulative = 0.0;
+    let n_cl = candidates
+        .into_iter()
+        .find(|&(w, _)| {
+            cumulative += w;
+            cumulative >= half
+        })
+        .map_or(0.0, |(_, n_cl_i)| n_cl_i.max(0.0));
+
+    let MgPerL(mg_l_cl) = MolPerL(n_cl).to_mg_per_l(M_CL);
+    mg_l_cl.max(MIN_CL_MG_L)
+}
+
+/// Ratio-based chloride estimate in mol/L (see `estimate_cl_mg_l_from_ratios`).
+fn n_cl_from_ratios(inp: &Inputs) -> f64 {
+    let (sum_w, sum_w_ncl) = ratio_cl_candidates(inp)
+        .into_iter()
+        .flatten()
+        .fold((0.0, 0.0), |(sw, swn), (w, n_cl_i)| {
+            (sw + w, swn + w * n_cl_i)
+        });
+
+    if sum_w > 0.0 {
+        (sum_w_ncl / sum_w).max(0.0)
+    } else {
+        0.0
+    }
+}
+
+/// Species entering the ratio-based chloride blend with their raw weights
+/// (reference abundance, mmol/kg), in `ratio_cl_candidates` order.
+const RATIO_BLEND_SPECIES: [(&str, f64); 7] = [
+    ("Na+", REF_MMOL_NA),
+    ("Mg2+", REF_MMOL_MG),
+    ("Ca2+", REF_MMOL_CA),
+    ("K+", REF_MMOL_K),
+    ("Sr2+", REF_MMOL_SR),
+    ("Br-", REF_MMOL_BR),
+    ("SO4^2-", REF_MMOL_SO4),
+];
+
+/// Normalized weight of each species in the ratio-based chloride estimate
+/// (`estimate_cl_mg_l_from_ratios`), summing to 1.
+///
+/// These are the weights for a panel reporting every species; species
+/// reported as zero/negative drop out and the rest are renormalized.
+pub fn ratio_blend_weights() -> Vec<(&'static str, f64)> {
+    let total: f64 = RATIO_BLEND_SPECIES.iter().map(|&(_, w)| w).sum();
+    RATIO_BLEND_SPECIES
+        .iter()
+        .map(|&(name, w)| (name, w / total))
+        .collect()
+}
+
+/// Per-species chloride candidates `(weight, n_cl_i)` in mol/L; `None` for
+/// species reported as zero/negative, so a zero never enters the ratio blend
+/// (with or without `Assumptions::treat_zero_as_missing`).
+fn ratio_cl_candidates(inp: &Inputs) -> [Option<(f64, f64)>; 7] {
+    // Sulfate mg/L (direct SO4 or converted from elemental S)
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+
+    // (measured mg/L, molar mass), weights from `RATIO_BLEND_SPECIES`
+    let measured = [
+        (inp.na, M_NA),
+        (inp.mg, M_MG),
+        (inp.ca, M_CA),
+        (inp.k, M_K),
+        (inp.sr, M_SR),
+        (inp.br, M_BR),
+        (so4_mg_l, M_SO4),
+    ];
+
+    // Reference molar ratio r_i = REF_MMOL_i / REF_MMOL_CL gives n_cl_i = n_i / r_i.
+    core::array::from_fn(|i| {
+        let (_, w) = RATIO_BLEND_SPECIES[i];
+        let (mg_l, molar_mass) = measured[i];
+        let n_i = mol_per_l(mg_l, molar_mass);
+        let r_i = w / REF_MMOL_CL;
+        (w > 0.0 && r_i > 0.0 && n_i > 0.0).then(|| (w, n_i / r_i))
+    })
+}
+
+/// Largest factor by which a single major ion may depart from the panel's
+/// mean reference scaling in `looks_like_seawater`.
+pub const SEAWATER_RATIO_TOLERANCE: f64 = 2.0;
+
+/// Whether the major-ion ratios of `inp` resemble seawater.
+///
+/// Each major ion (Na, Mg, Ca, K, SO4, and Cl when measured) implies a
+/// scaling `n_i / REF_MMOL_i` of the reference composition. The panel passes
+/// when every scaling is positive and within `SEAWATER_RATIO_TOLERANCE` of
+/// their mean, which rejects freshwater (Ca/Mg-dominated) and single-salt
+/// solutions (missing major ions) while tolerating reef-tank style dosing.
+pub fn looks_like_seawater(inp: &Inputs) -> bool {
+    looks_like_seawater_with(inp, false)
+}
+
+/// `looks_like_seawater`, optionally skipping ions reported as exactly zero
+/// (`Assumptions::treat_zero_as_missing`) instead of counting them as a
+/// measured absence.
+pub fn looks_like_seawater_with(inp: &Inputs, treat_zero_as_missing: bool) -> bool {
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+    let scale = |mg_l: f64, molar_mass: f64, ref_mmol: f64| {
+        (!treat_zero_as_missing || mg_l != 0.0).then(|| mol_per_l(mg_l, molar_mass) / ref_mmol)
+    };
+    let scales = [
+        scale(inp.na, M_NA, REF_MMOL_NA),
+        scale(inp.mg, M_MG, REF_MMOL_MG),
+        scale(inp.ca, M_CA, REF_MMOL_CA),
+        scale(inp.k, M_K, REF_MMOL_K),
+        scale(so4_mg_l, M_SO4, REF_MMOL_SO4),
+        inp.cl.and_then(|cl| scale(cl, M_CL, REF_MMOL_CL)),
+    ];
+    let scales = || scales.iter().flatten().copied();
+
+    let mean = scales().sum::<f64>() / scales().count() as f64;
+    mean > 0.0
+        && scales()
+            .all(|s| s >= mean / SEAWATER_RATIO_TOLERANCE && s <= mean * SEAWATER_RATIO_TOLERANCE)
+}
+
+/// Concentrations at or below this (mg/L, or dKH for alkalinity) count as
+/// absent in `is_zero_panel`.
+pub const ZERO_PANEL_THRESHOLD: f64 = 1e-9;
+
+/// True when `inp` reports no ions and no alkalinity (every value missing or
+/// at most `ZERO_PANEL_THRESHOLD`), e.g. an all-zero `Inputs::default()`.
+///
+/// Such a panel has no salinity to solve for; the calculators return SP = 0
+/// and pure-water density for it instead of normalizing by zero, and
+/// `compute_full` reports `Warning::ZeroPanel`.
+pub fn is_zero_panel(inp: &Inputs) -> bool {
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+    [
+        inp.na,
+        inp.ca,
+        inp.mg,
+        inp.k,
+        inp.sr,
+        inp.br,
+        inp.b,
+        so4_mg_l,
+        inp.cl.unwrap_or(0.0),
+        inp.f.unwrap_or(0.0),
+        inp.measured_alk_dkh().unwrap_or(0.0),
+        inp.gh_dgh.unwrap_or(0.0),
+        custom_mass_mg_l(inp),
+    ]
+    .iter()
+    .all(|&v| v <= ZERO_PANEL_THRESHOLD)
+}
+
+/// Median of the major-ion scalings `n_i / REF_MMOL_i` (Na, Mg, Ca, K, SO4,
+/// and Cl when positive), in (mol/L)/(mmol/kg).
+///
+/// Multiplied by 1000 this is the panel's salinity relative to the SP = 35
+/// reference times its kg/L, robust against one or two off-ratio ions.
+/// Zero for an empty panel.
+pub fn median_reference_scale(inp: &Inputs) -> f64 {
+    let (so4_mg_l, _) = sulfate_mg_l(inp);
+    let cl = inp.cl.filter(|&c| c > 0.0);
+    let mut scales = [
+        mol_per_l(inp.na, M_NA) / REF_MMOL_NA,
+        mol_per_l(inp.mg, M_MG) / REF_MMOL_MG,
+        mol_per_l(inp.ca, M_CA) / REF_MMOL_CA,
+        mol_per_l(inp.k, M_K) / REF_MMOL_K,
+        mol_per_l(so4_mg_l, M_SO4) / REF_MMOL_SO4,
+        cl.map_or(f64::NAN, |c| mol_per_l(c, M_CL) / REF_MMOL_CL),
+    ];
+    let n = if cl.is_some() { 6 } else { 5 };
+    let scales = &mut scales[..n];
+    scales.sort_by(f64::total_cmp);
+    if n % 2 == 0 {
+        0.5 * (scales[n / 2 - 1] + scales[n / 2])
+    } else {
+        scales[n / 2]
+    }
+}
+
 /// Estimate chloride (mg/L) combining charge balance and reference ion ratio blending.
 ///
 /// Strategy:
 /// 1. Charge balance ignoring unknown Cl yields `n_cl_charge`.
-/// 2. Ratio-based candidates: derive Cl from other measured ions using reference molar ratios.
+/// 2. Ratio-based candidates: derive Cl from other measured ions using reference molar ratios
+///    (see `estimate_cl_mg_l_from_ratios`).
 /// 3. Adaptive blend: if charge estimate < 80% of ratio estimate, prefer ratio entirely; else weighted blend (alpha=0.6).
 ///
 /// Inputs:
 /// - `inp`: measured ion mass concentrations (mg/L).
-/// - `default_f_mg_l`: fallback fluoride mg/L if not provided.
-/// - `n_borate, n_hco3, n_co3, n_oh`: mol/L contributions from speciation helpers.
+/// - `ctx`: fallback fluoride mg/L if not provided, mol/L contributions from
+///   speciation helpers, and the temperature and salinity for HF dissociation.
 ///
 /// Returns chloride mg/L >= 0.
-pub fn estimate_cl_mg_l(
-    inp: &Inputs,
-    default_f_mg_l: f64,
-    n_borate: f64,
-    n_hco3: f64,
-    n_co3: f64,
-    n_oh: f64,
-) -> f64 {
+pub fn estimate_cl_mg_l(inp: &Inputs, ctx: &EstimationContext) -> f64 {
     // 1) Charge-balance-based estimate (mol/L)
-    let mg_l_charge =
-        estimate_cl_mg_l_from_charge_balance(inp, default_f_mg_l, n_borate, n_hco3, n_co3, n_oh);
-    // convert mg/L -> mol/L and divide by chloride molar mass (M_CL is constant > 0)
-    let n_cl_charge = (mg_l_charge / 1000.0) / M_CL;
+    let mg_l_charge = estimate_cl_mg_l_from_charge_balance(inp, ctx);
+    let MolPerL(n_cl_charge) = MgPerL(mg_l_charge).to_mol_per_l(M_CL);
 
     // 2) Ratio-based candidates (mol/L)
-    // Reference molar ratios r_i = REF_MMOL_i / REF_MMOL_CL
-    let r_na = REF_MMOL_NA / REF_MMOL_CL;
-    let r_mg = REF_MMOL_MG / REF_MMOL_CL;
-    let r_ca = REF_MMOL_CA / REF_MMOL_CL;
-    let r_k = REF_MMOL_K / REF_MMOL_CL;
-    let r_sr = REF_MMOL_SR / REF_MMOL_CL;
-    let r_br = REF_MMOL_BR / REF_MMOL_CL;
-    let r_so4 = REF_MMOL_SO4 / REF_MMOL_CL;
-
-    // Measured moles per L for species with reliable ratios
-    // Inputs are in mg/L – mol_per_l expects mg/L
-    let n_na = mol_per_l(inp.na, M_NA);
-    let n_mg = mol_per_l(inp.mg, M_MG);
-    let n_ca = mol_per_l(inp.ca, M_CA);
-    let n_k = mol_per_l(inp.k, M_K);
-    let n_sr = mol_per_l(inp.sr, M_SR);
-    let n_br = mol_per_l(inp.br, M_BR);
-    // Convert S (mg/L as S) to SO4 mg/L by molar mass ratio
-    let so4_mg_l = (inp.s / M_S) * M_SO4;
-    let n_so4 = mol_per_l(so4_mg_l, M_SO4);
-
-    // Candidate n_cl from each species (ignore invalid/zero) using an iterator-based fold.
-    let species: &[(f64, f64, f64)] = &[
-        (REF_MMOL_NA, n_na, r_na),
-        (REF_MMOL_MG, n_mg, r_mg),
-        (REF_MMOL_CA, n_ca, r_ca),
-        (REF_MMOL_K, n_k, r_k),
-        (REF_MMOL_SR, n_sr, r_sr),
-        (REF_MMOL_BR, n_br, r_br),
-        (REF_MMOL_SO4, n_so4, r_so4),
-    ];
+    let n_cl_ratio = n_cl_from_ratios(inp);
 
-    let (sum_w, sum_w_ncl) = species
-        .iter()
-        .fold((0.0, 0.0), |(sw, swn), &(w, n_i, r_i)| {
-            if w > 0.0 && r_i > 0.0 && n_i > 0.0 {
-                let n_cl_i = n_i / r_i;
-                (sw + w, swn + w * n_cl_i)
-            } else {
-                (sw, swn)
-            }
-        });
+    // 3) Adaptive blend
+    let MgPerL(mg_l_cl) = MolPerL(blend_n_cl(n_cl_charge, n_cl_ratio)).to_mg_per_l(M_CL);
+    mg_l_cl.max(MIN_CL_MG_L)
+}
 
-    let n_cl_ratio = if sum_w > 0.0 {
-        (sum_w_ncl / sum_w).max(0.0)
-    } else {
-        0.0
-    };
+/// Chloride estimate with a plausibility band, as `(low, best, high)` mg/L.
+///
+/// `best` is `estimate_cl_mg_l`. `low`/`high` span the charge-balance
+/// estimate and every per-species ratio candidate (see
+/// `estimate_cl_mg_l_from_ratios`), so a wide band flags a panel whose ions
+/// disagree with each other or with electroneutrality.
+pub fn estimate_cl_mg_l_interval(inp: &Inputs, ctx: &EstimationContext) -> (f64, f64, f64) {
+    let best = estimate_cl_mg_l(inp, ctx);
+    let charge = estimate_cl_mg_l_from_charge_balance(inp, ctx);
+
+    let (low, high) = ratio_cl_candidates(inp)
+        .into_iter()
+        .flatten()
+        .map(|(_, n_cl_i)| MolPerL(n_cl_i).to_mg_per_l(M_CL).0)
+        .chain([charge, best])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+    (low, best, high)
+}
 
-    // 3) Adaptive blend:
-    // If the charge-balance estimate is significantly lower than the ratio-based estimate (underestimation), use the ratio estimate entirely.
-    // Otherwise, apply a moderate blend.
-    let n_cl_blend = if n_cl_ratio > 0.0 {
+/// Adaptive blend of charge-balance and ratio-based chloride (both mol/L).
+///
+/// If the charge-balance estimate is significantly lower than the ratio-based
+/// estimate (underestimation), use the ratio estimate entirely. Otherwise,
+/// apply a moderate blend.
+pub(crate) fn blend_n_cl(n_cl_charge: f64, n_cl_ratio: f64) -> f64 {
+    if n_cl_ratio > 0.0 {
         if n_cl_charge < RATIO_BLEND_THRESHOLD * n_cl_ratio {
             n_cl_ratio
         } else {
@@ -330,9 +783,7 @@ pub fn estimate_cl_mg_l(
         }
     } else {
         n_cl_charge
-    };
-
-    (n_cl_blend * M_CL * 1000.0).max(MIN_CL_MG_L)
+    }
 }
 
 /// Compute adjusted reference total mass (g/kg) including boron species transformation and optional reference alkalinity.
@@ -381,6 +832,31 @@ pub fn ref_sum_with_boron_species_and_ref_alk(
     (sum_ref_gkg_base - ref_b_element_gkg) + ref_b_species_gkg + ref_alk_gkg
 }
 
+/// Redfield C:P ratio used to link remineralized DIC to phosphate.
+pub const REDFIELD_C_TO_P: f64 = 106.0;
+
+/// Absolute salinity anomaly (g/kg) caused by dissolved nutrients.
+///
+/// Uses the TEOS-10 composition-anomaly relation (Pawlowicz et al., 2011)
+///
+/// δSA = 55.6 ΔTA + 4.7 ΔDIC + 38.9 ΔNO3 + 50.7 ΔSi(OH)4   (g/kg per mol/kg)
+///
+/// with the carbonate terms tied to the nutrients by Redfield-style
+/// remineralization: ΔDIC = 106 ΔPO4 and ΔTA = −(ΔNO3 + ΔPO4).
+///
+/// Inputs are µmol/kg; negative values are treated as 0. Zero nutrients give
+/// zero correction; deep Pacific values (Si ~150, NO3 ~40, PO4 ~3) give a
+/// δSA of order 0.01 g/kg.
+pub fn sa_nutrient_correction(si: f64, no3: f64, po4: f64) -> f64 {
+    let si = si.max(0.0) * 1e-6;
+    let no3 = no3.max(0.0) * 1e-6;
+    let po4 = po4.max(0.0) * 1e-6;
+
+    let d_ta = -(no3 + po4);
+    let d_dic = REDFIELD_C_TO_P * po4;
+    55.6 * d_ta + 4.7 * d_dic + 38.9 * no3 + 50.7 * si
+}
+
 /// Round a floating-point value to a specified number of decimal digits.
 ///
 /// Inputs:
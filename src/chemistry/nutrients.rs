@@ -0,0 +1,45 @@
+//! Silicate and phosphate alkalinity.
+//!
+//! At seawater pH a small part of dissolved silicate is present as
+//! SiO(OH)3- and most phosphate as HPO4^2-, so both add to total alkalinity
+//! beyond the carbonate system (Dickson 1981). The contributions are a few
+//! µeq/kg in nutrient-rich water and only evaluated when `Inputs::ph` is
+//! given; like `fluoride`, the constants are taken at 25 °C and S = 35 and
+//! applied to the free-scale `Inputs::ph` (the ~0.1 offset to the total
+//! scale of the constants is immaterial for terms this small).
+
+use crate::models::Inputs;
+
+/// pK of Si(OH)4 ⇌ SiO(OH)3- + H+ at 25 °C, S = 35 (Millero 1995).
+pub const PK_SI: f64 = 9.38;
+/// pK1, pK2, pK3 of phosphoric acid at 25 °C, S = 35 (Millero 1995).
+pub const PK_P: [f64; 3] = [1.61, 5.96, 8.79];
+
+/// Silicate alkalinity [SiO(OH)3-] (µeq/kg) of `si_umol_kg` total silicate
+/// at `ph`.
+pub fn silicate_alkalinity(si_umol_kg: f64, ph: f64) -> f64 {
+    let k_si = 10f64.powf(-PK_SI);
+    let h = 10f64.powf(-ph);
+    si_umol_kg.max(0.0) * k_si / (k_si + h)
+}
+
+/// Phosphate alkalinity [HPO4^2-] + 2[PO4^3-] − [H3PO4] (µeq/kg) of
+/// `po4_umol_kg` total phosphate at `ph`; H2PO4- is the zero level.
+pub fn phosphate_alkalinity(po4_umol_kg: f64, ph: f64) -> f64 {
+    let [k1, k2, k3] = PK_P.map(|pk| 10f64.powf(-pk));
+    let h = 10f64.powf(-ph);
+    let denom = h * h * h + k1 * h * h + k1 * k2 * h + k1 * k2 * k3;
+    po4_umol_kg.max(0.0) * (k1 * k2 * h + 2.0 * k1 * k2 * k3 - h * h * h) / denom
+}
+
+/// Silicate plus phosphate alkalinity (meq/L) of `inp`; zero without
+/// `Inputs::ph`. Nutrients are µmol/kg and are taken per litre here, which
+/// is well within the accuracy of these few-µeq terms.
+pub fn nutrient_alkalinity_meq_l(inp: &Inputs) -> f64 {
+    let Some(ph) = inp.ph else {
+        return 0.0;
+    };
+    let si = silicate_alkalinity(inp.si_umol_kg.unwrap_or(0.0), ph);
+    let p = phosphate_alkalinity(inp.po4_umol_kg.unwrap_or(0.0), ph);
+    (si + p) / 1000.0
+}
@@ -0,0 +1,32 @@
+//! Unit-tagged concentrations for the chemistry conversions.
+//!
+//! The chemistry layer juggles mg/L, mol/L and mmol/kg as bare `f64`s, where
+//! a missing `* 1000.0` goes unnoticed. These wrappers carry the unit in the
+//! type so a mass concentration cannot be passed where an amount is expected;
+//! the public `f64` helpers (`mol_per_l`, …) are thin wrappers around them.
+
+use super::TINY;
+
+/// Mass concentration in mg/L.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct MgPerL(pub f64);
+
+/// Amount concentration in mol/L (not mmol/L).
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct MolPerL(pub f64);
+
+impl MgPerL {
+    /// Amount concentration for a species of molar mass `molar_mass_g_mol`.
+    /// Negative concentrations count as 0 and the molar mass is guarded
+    /// against division by ~0 via `TINY`.
+    pub fn to_mol_per_l(self, molar_mass_g_mol: f64) -> MolPerL {
+        MolPerL(self.0.max(0.0) / 1000.0 / molar_mass_g_mol.max(TINY))
+    }
+}
+
+impl MolPerL {
+    /// Mass concentration for a species of molar mass `molar_mass_g_mol`.
+    pub fn to_mg_per_l(self, molar_mass_g_mol: f64) -> MgPerL {
+        MgPerL(self.0 * molar_mass_g_mol * 1000.0)
+    }
+}
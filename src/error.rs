@@ -52,6 +52,34 @@ pub enum AppError {
     #[error("Computation produced non-finite values; please verify inputs and assumptions")]
     NonFiniteOutput,
 
+    #[cfg(feature = "cli")]
+    #[error("Invalid --sp-range: expected 0 <= MIN <= MAX, got {min} {max}")]
+    InvalidSpRange { min: f64, max: f64 },
+
+    #[cfg(feature = "cli")]
+    #[error("Unknown --fields name '{name}' (valid: {valid})")]
+    UnknownField { name: String, valid: String },
+
+    #[cfg(feature = "cli")]
+    #[error("Batch input line {line}: {source}")]
+    BatchLine {
+        line: usize,
+        #[source]
+        source: Box<AppError>,
+    },
+
+    #[cfg(feature = "sqlite")]
+    #[error("Unsupported --output kind '{kind}' (expected 'sqlite')")]
+    UnsupportedOutput { kind: String },
+
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error for '{path}': {source}")]
+    Sqlite {
+        path: String,
+        #[source]
+        source: rusqlite::Error,
+    },
+
     #[error("Unexpected error: {0}")]
     Other(String),
 
@@ -66,6 +94,53 @@ pub enum AppError {
     MissingAssumptions,
 }
 
+#[cfg(feature = "std")]
+impl AppError {
+    /// Stable snake_case identifier of the variant, for programmatic matching.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "cli")]
+            AppError::ReadStdin { .. } => "read_stdin",
+            #[cfg(feature = "cli")]
+            AppError::ReadFile { .. } => "read_file",
+            #[cfg(feature = "cli")]
+            AppError::ParseInputsJson { .. } => "parse_inputs_json",
+            #[cfg(feature = "cli")]
+            AppError::ParseAssumptionsJson { .. } => "parse_assumptions_json",
+            #[cfg(feature = "cli")]
+            AppError::ParseCmdInputJson { .. } => "parse_cmd_input_json",
+            #[cfg(feature = "cli")]
+            AppError::SerializeOutput { .. } => "serialize_output",
+            #[cfg(feature = "cli")]
+            AppError::NonFiniteOutput => "non_finite_output",
+            #[cfg(feature = "cli")]
+            AppError::InvalidSpRange { .. } => "invalid_sp_range",
+            #[cfg(feature = "cli")]
+            AppError::UnknownField { .. } => "unknown_field",
+            #[cfg(feature = "cli")]
+            AppError::BatchLine { .. } => "batch_line",
+            #[cfg(feature = "sqlite")]
+            AppError::UnsupportedOutput { .. } => "unsupported_output",
+            #[cfg(feature = "sqlite")]
+            AppError::Sqlite { .. } => "sqlite",
+            AppError::Other(_) => "other",
+            #[cfg(feature = "cli")]
+            AppError::MissingInputData => "missing_input_data",
+            #[cfg(feature = "cli")]
+            AppError::MissingAssumptions => "missing_assumptions",
+        }
+    }
+
+    /// Machine-readable form `{ "kind": ..., "message": ... }`, where `kind`
+    /// is `AppError::kind` and `message` the `Display` text.
+    pub fn to_error_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "kind": self.kind(),
+            "message": self.to_string(),
+        })
+    }
+}
+
 #[cfg(not(feature = "std"))]
 #[derive(Debug)]
 pub enum AppError {
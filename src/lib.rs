@@ -8,13 +8,42 @@ pub mod chemistry;
 #[cfg(feature = "std")]
 pub mod error;
 pub mod models;
+pub(crate) mod numeric;
 pub mod salinity;
+pub mod warning;
 
-pub use crate::adapters::teos10::sa_from_sp;
+pub use crate::adapters::teos10::{
+    c_from_sp, conductivity_ms_cm, sa_from_conductivity, sa_from_sp, sa_from_sp_location,
+    sp_conductivity_temperature_sweep, sp_from_c, sp_from_conductivity,
+};
+pub use crate::chemistry::Species;
 #[cfg(feature = "std")]
 pub use crate::error::AppError;
-pub use crate::models::{Assumptions, Inputs};
+pub use crate::models::{
+    Assumptions, ConcentrationBasis, ConcentrationUnit, CustomIon, EosKind, Inputs, OutputPrecision,
+};
+pub use crate::salinity::blend::{blend_ratio_for_target_sp, mix};
 pub use crate::salinity::calculator::{
-    CalcResult, Components, DetailedResult, calc_salinity_sp_iterative, calc_salinity_sp_teos10,
-    compute_summary, rho_from_sp, specific_gravity,
+    CalcResult, Components, ConfigUsed, DetailedResult, FullResult, calc_all,
+    calc_salinity_sp_iterative, calc_salinity_sp_iterative_with_estimator,
+    calc_salinity_sp_iterative_with_models, calc_salinity_sp_teos10, compressibility, compute_full,
+    compute_summaries, compute_summary, effective_ref_sum, freezing_margin, mass_closure_pct,
+    nonconservative_salinity_contribution, rho_from_sp, sa_from_composition, salinity_ratio,
+    salt_mass_kg, sp_from_density, specific_gravity, specific_gravity_air_corrected,
+    specific_gravity_from_densities, specific_gravity_in_air, specific_gravity_with_epsilon,
+    tds_mg_l,
+};
+pub use crate::salinity::instrument::{Instrument, instrument_reading, refractive_index};
+pub use crate::salinity::lut::{Lut, build_salinity_lut};
+pub use crate::salinity::profile::{density_anomaly, sp_anomaly};
+pub use crate::salinity::properties::{Teos10Properties, teos10_properties};
+pub use crate::salinity::sensitivity::{
+    estimated_sp_bias, minimal_ion_set, sp_sensitivities, sp_without_ion,
+};
+pub use crate::salinity::standard::{
+    PanelReconstruction, recipe_density, reconstruct_panel, reconstruct_panel_checked,
+    standard_seawater,
 };
+pub use crate::salinity::stats::{BatchStats, Stat, batch_statistics};
+pub use crate::salinity::sweep::{summary_sweep_pressure, summary_sweep_temperature};
+pub use crate::warning::Warning;
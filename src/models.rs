@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Assumptions {
@@ -14,6 +17,132 @@ pub struct Assumptions {
     pub borate_fraction: Option<f64>,
     pub alk_mg_per_meq: Option<f64>,
     pub rn_compat: bool,
+    /// Diagnostic: hold SP at this value instead of solving for it; density
+    /// and components are evaluated there from the ion panel.
+    pub fixed_sp: Option<f64>,
+    /// Unit of the `Inputs` ion concentrations (default mg/L).
+    pub input_units: ConcentrationUnit,
+    /// Molar Ca:Mg ratio used to split `Inputs::gh_dgh` when neither Ca nor
+    /// Mg is given; `None` uses the seawater reference ratio.
+    pub gh_ca_mg_ratio: Option<f64>,
+    /// Decimal places used for rounding SP and for CLI output.
+    pub precision: OutputPrecision,
+    /// Equation of state for the in-situ density.
+    pub eos: EosKind,
+    /// Include the effective configuration (`config_used`) in `compute_full`
+    /// results.
+    pub echo_config: bool,
+    /// Evaluate the summary SG 20/20 and SG 25/25 at `pressure_dbar` instead
+    /// of 0 dbar.
+    pub sg_at_sample_pressure: bool,
+    /// Treat ions reported as exactly 0.0 as not measured in the seawater
+    /// ratio check instead of as a measured absence. Ratio-based chloride
+    /// candidates skip zeros either way.
+    pub treat_zero_as_missing: bool,
+    /// Add a `Warning::PossibleContamination` for each salt that
+    /// `diagnose_contamination` suspects.
+    pub warn_contamination: bool,
+    /// Alkalinity (dKH) above which `compute_full` warns of a likely
+    /// data-entry error; the value is still used as given.
+    pub alk_ceiling_dkh: f64,
+    /// Report HCO3-, CO3^2- and OH- (mg/L of each ion) in the component
+    /// tables instead of the lumped `Alk.` row. Display only: the mass
+    /// budget and SP are unchanged.
+    pub expand_alk_species: bool,
+    /// Fraction (0–1, by volume) of the water that is D2O. Scales the
+    /// in-situ density by `1 + d2o_fraction * (D2O_DENSITY_RATIO - 1)`;
+    /// `None` (default) leaves it untouched. Specific gravities are ratios
+    /// to equally labelled water and do not change.
+    pub d2o_fraction: Option<f64>,
+    /// Species names (e.g. `"Cl-"`) listed first, in this order, in the
+    /// component tables; the rest follow in the default order and unknown
+    /// names are ignored. `None` keeps the default order.
+    pub species_order: Option<Vec<String>>,
+    /// Independently measured sample density (kg/m³). When set, the solver
+    /// uses it for the mg/L → g/kg conversion (and reports it as the detailed
+    /// `rho_kg_m3`) instead of the equation of state; `None` (default) uses
+    /// the computed density.
+    pub measured_density: Option<f64>,
+    /// Absolute Salinity anomaly δSA (g/kg) added to the Reference Salinity
+    /// (with any nutrient correction) to report SA, e.g. a regional
+    /// correction where no SAAR lookup is available. `None` gives SA = SR.
+    pub delta_sa: Option<f64>,
+    /// Whether the `Inputs` ion concentrations are per litre (default) or per
+    /// kilogram of sample.
+    pub input_basis: ConcentrationBasis,
+    /// Also report the composition-derived SA
+    /// (`CalculationSummary::sa_composition_g_kg`).
+    pub report_composition_sa: bool,
+    /// Record the SP of every solver iteration in
+    /// `DetailedResult::iteration_history` (off by default to avoid the
+    /// allocation).
+    pub record_history: bool,
+}
+
+/// Equation of state used for density in the solver and `rho_from_sp`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EosKind {
+    /// TEOS-10 (`gsw`), validated to SA ≈ 42 g/kg.
+    #[default]
+    Teos10,
+    /// Sharqawy et al. (2010) brine correlation (see `adapters::brine`),
+    /// valid to 160 g/kg and 180 °C but at atmospheric pressure only;
+    /// `pressure_dbar` is ignored.
+    BrineExtended,
+}
+
+/// Decimal places per output field.
+///
+/// `sp` also sets the rounding of the solver's SP (and normalized SP);
+/// the other fields only affect formatting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputPrecision {
+    pub sp: usize,
+    pub sa: usize,
+    pub density: usize,
+    pub sg: usize,
+    pub components: usize,
+}
+
+impl Default for OutputPrecision {
+    fn default() -> Self {
+        Self {
+            sp: 4,
+            sa: 4,
+            density: 3,
+            sg: 5,
+            components: 3,
+        }
+    }
+}
+
+/// Unit of the ion concentrations in `Inputs`.
+///
+/// Applies to `na, ca, mg, k, sr, br, cl, f, s, so4, b`; alkalinity keeps its
+/// own units (dKH / ppm CaCO3) and nutrients stay in µmol/kg.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcentrationUnit {
+    #[default]
+    MgPerL,
+    MmolPerL,
+}
+
+/// Reference amount of sample for the ion concentrations in `Inputs`.
+///
+/// `PerKilogram` values (mg/kg or mmol/kg, see `ConcentrationUnit`) are
+/// converted to per litre with the in-situ density of the sample, which is
+/// itself solved together with SP. It applies to the same ions as
+/// `ConcentrationUnit`; alkalinity, GH and the `default_f_mg_l` fallback stay
+/// per litre.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcentrationBasis {
+    #[default]
+    PerLiter,
+    PerKilogram,
 }
 
 impl Default for Assumptions {
@@ -30,11 +159,29 @@ impl Default for Assumptions {
             borate_fraction: None,
             alk_mg_per_meq: None,
             rn_compat: false,
+            fixed_sp: None,
+            input_units: ConcentrationUnit::MgPerL,
+            gh_ca_mg_ratio: None,
+            precision: OutputPrecision::default(),
+            eos: EosKind::Teos10,
+            echo_config: false,
+            sg_at_sample_pressure: false,
+            treat_zero_as_missing: false,
+            warn_contamination: false,
+            alk_ceiling_dkh: crate::chemistry::ALK_CEILING_DKH_DEFAULT,
+            expand_alk_species: false,
+            d2o_fraction: None,
+            species_order: None,
+            measured_density: None,
+            delta_sa: None,
+            input_basis: ConcentrationBasis::PerLiter,
+            report_composition_sa: false,
+            record_history: false,
         }
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Inputs {
     pub na: f64,
     pub ca: f64,
@@ -45,11 +192,205 @@ pub struct Inputs {
     pub cl: Option<f64>,
     pub f: Option<f64>,
     pub s: f64,
+    /// Sulfate reported directly as SO4 (mg/L). When present it is used
+    /// instead of converting elemental `s`.
+    pub so4: Option<f64>,
     pub b: f64,
     pub alk_dkh: Option<f64>,
+    /// Dissolved silicate Si(OH)4 in µmol/kg (optional nutrient).
+    pub si_umol_kg: Option<f64>,
+    /// Nitrate NO3- in µmol/kg (optional nutrient).
+    pub no3_umol_kg: Option<f64>,
+    /// Phosphate PO4 in µmol/kg (optional nutrient).
+    pub po4_umol_kg: Option<f64>,
+    /// Alkalinity as ppm (mg/L) CaCO3, as reported by many hobby test kits.
+    /// Only used when `alk_dkh` is absent.
+    pub alk_ppm_caco3: Option<f64>,
+    /// General hardness (Ca + Mg) in dGH. Only used when `ca` and `mg` are
+    /// not both given; see `Inputs::with_gh_apportioned`.
+    pub gh_dgh: Option<f64>,
+    /// Dilution of the measured aliquot (10 = diluted 1:10); all
+    /// concentrations are multiplied by it, see `Inputs::undiluted`.
+    pub dilution_factor: Option<f64>,
+    /// Sample pH (free scale). When given, fluoride present as HF⁰ is left
+    /// out of the charge balance (`chemistry::fluoride`).
+    pub ph: Option<f64>,
+    /// In-situ temperature (°C) of this sample; overrides
+    /// `Assumptions::temp` when present (see `Assumptions::at_sample_conditions`).
+    pub t_c: Option<f64>,
+    /// Sea pressure (dbar) of this sample; overrides
+    /// `Assumptions::pressure_dbar` when present.
+    pub p_dbar: Option<f64>,
+    /// Additional solutes outside the fixed ion set (e.g. NH4+, NO2-); they
+    /// count in the charge balance and the mass budget.
+    pub custom: Option<Vec<CustomIon>>,
+}
+
+/// A charged solute not covered by the `Inputs` fields.
+///
+/// `mg_l` is a mass concentration on the panel's basis (mg/L, or mg/kg with
+/// `ConcentrationBasis::PerKilogram`); `input_units` does not apply. It
+/// follows `dilution_factor` like the other ions. `charge` is signed, e.g.
+/// +1 for NH4+; zero-charge solutes only add mass.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomIon {
+    pub name: String,
+    pub mg_l: f64,
+    /// Molar mass (g/mol).
+    pub molar_mass: f64,
+    pub charge: i32,
+}
+
+impl CustomIon {
+    /// This ion with `mg_l` multiplied by `factor`.
+    pub fn scaled(&self, factor: f64) -> CustomIon {
+        CustomIon {
+            mg_l: self.mg_l * factor,
+            ..self.clone()
+        }
+    }
+}
+
+impl Inputs {
+    /// Measured alkalinity in dKH, if any was supplied.
+    ///
+    /// Precedence: `alk_dkh` wins over `alk_ppm_caco3`; the latter is converted
+    /// via `dkh_from_ppm_caco3`. Returns `None` when neither is given so the
+    /// caller can fall back to `Assumptions::alkalinity`.
+    pub fn measured_alk_dkh(&self) -> Option<f64> {
+        self.alk_dkh
+            .or_else(|| self.alk_ppm_caco3.map(crate::chemistry::dkh_from_ppm_caco3))
+    }
+
+    /// The `custom` ions, if any.
+    pub fn custom_ions(&self) -> impl Iterator<Item = &CustomIon> {
+        self.custom.iter().flatten()
+    }
+
+    /// `custom` with every `mg_l` multiplied by `factor`.
+    pub fn custom_scaled(&self, factor: f64) -> Option<Vec<CustomIon>> {
+        self.custom
+            .as_ref()
+            .map(|ions| ions.iter().map(|ion| ion.scaled(factor)).collect())
+    }
+
+    /// This panel scaled back to the original sample by `dilution_factor`.
+    ///
+    /// Every concentration (ions, custom ions, alkalinity, GH, nutrients) is
+    /// multiplied by the factor and the result carries no factor, so applying
+    /// this twice is harmless. Missing, non-finite or non-positive factors
+    /// leave the panel unchanged.
+    ///
+    /// The solver undoes the dilution first, then converts units with
+    /// `to_mg_per_l` (per `Assumptions::input_units`) and finally apportions
+    /// GH with `with_gh_apportioned`.
+    pub fn undiluted(&self) -> Inputs {
+        let Some(k) = self.dilution_factor.filter(|k| k.is_finite() && *k > 0.0) else {
+            return Inputs {
+                dilution_factor: None,
+                ..self.clone()
+            };
+        };
+        let scale = |v: f64| v * k;
+        Inputs {
+            na: scale(self.na),
+            ca: scale(self.ca),
+            mg: scale(self.mg),
+            k: scale(self.k),
+            sr: scale(self.sr),
+            br: scale(self.br),
+            cl: self.cl.map(scale),
+            f: self.f.map(scale),
+            s: scale(self.s),
+            so4: self.so4.map(scale),
+            b: scale(self.b),
+            alk_dkh: self.alk_dkh.map(scale),
+            si_umol_kg: self.si_umol_kg.map(scale),
+            no3_umol_kg: self.no3_umol_kg.map(scale),
+            po4_umol_kg: self.po4_umol_kg.map(scale),
+            alk_ppm_caco3: self.alk_ppm_caco3.map(scale),
+            gh_dgh: self.gh_dgh.map(scale),
+            dilution_factor: None,
+            custom: self.custom_scaled(k),
+            ..self.clone()
+        }
+    }
+
+    /// This panel with Ca and Mg (mg/L) derived from `gh_dgh` when they are
+    /// not both given (a value is "given" when positive).
+    ///
+    /// GH is converted with `DGH_TO_MMOL_L` to total Ca + Mg in mmol/L. If one
+    /// of Ca/Mg is given, the other receives the remainder (floored at zero);
+    /// if neither is, the total is split at the molar ratio `ca_per_mg`
+    /// (Ca:Mg). Panels without `gh_dgh`, or with both Ca and Mg, are returned
+    /// unchanged.
+    pub fn with_gh_apportioned(&self, ca_per_mg: f64) -> Inputs {
+        use crate::chemistry::{DGH_TO_MMOL_L, M_CA, M_MG};
+        let Some(gh) = self.gh_dgh else {
+            return self.clone();
+        };
+        let total_mmol = gh.max(0.0) * DGH_TO_MMOL_L;
+        let (ca_mmol, mg_mmol) = match (self.ca > 0.0, self.mg > 0.0) {
+            (true, true) => return self.clone(),
+            (true, false) => {
+                let ca_mmol = self.ca / M_CA;
+                (ca_mmol, (total_mmol - ca_mmol).max(0.0))
+            }
+            (false, true) => {
+                let mg_mmol = self.mg / M_MG;
+                ((total_mmol - mg_mmol).max(0.0), mg_mmol)
+            }
+            (false, false) => {
+                let r = ca_per_mg.max(0.0);
+                (total_mmol * r / (1.0 + r), total_mmol / (1.0 + r))
+            }
+        };
+        Inputs {
+            ca: ca_mmol * M_CA,
+            mg: mg_mmol * M_MG,
+            ..self.clone()
+        }
+    }
+
+    /// This panel with ion concentrations expressed in mg/L, given that they
+    /// are currently in `unit`. Sulfur (`s`) and boron (`b`) are converted on
+    /// the elemental basis, `so4` as the SO4 ion.
+    pub fn to_mg_per_l(&self, unit: ConcentrationUnit) -> Inputs {
+        use crate::chemistry::*;
+        match unit {
+            ConcentrationUnit::MgPerL => self.clone(),
+            ConcentrationUnit::MmolPerL => Inputs {
+                na: self.na * M_NA,
+                ca: self.ca * M_CA,
+                mg: self.mg * M_MG,
+                k: self.k * M_K,
+                sr: self.sr * M_SR,
+                br: self.br * M_BR,
+                cl: self.cl.map(|v| v * M_CL),
+                f: self.f.map(|v| v * M_F),
+                s: self.s * M_S,
+                so4: self.so4.map(|v| v * M_SO4),
+                b: self.b * M_B,
+                ..self.clone()
+            },
+        }
+    }
 }
 
 impl Assumptions {
+    /// These assumptions at the conditions carried by `inp`: `inp.t_c` and
+    /// `inp.p_dbar`, when present, replace `temp` and `pressure_dbar`.
+    ///
+    /// Per-sample conditions take precedence so a batch can share one set of
+    /// assumptions; the calculation entry points apply this themselves.
+    pub fn at_sample_conditions(&self, inp: &Inputs) -> Assumptions {
+        Assumptions {
+            temp: inp.t_c.unwrap_or(self.temp),
+            pressure_dbar: inp.p_dbar.unwrap_or(self.pressure_dbar),
+            ..self.clone()
+        }
+    }
+
     pub fn normalized(mut self) -> Self {
         if !self.salinity_norm.is_finite() || self.salinity_norm <= 0.0 {
             self.salinity_norm = 35.0;
@@ -0,0 +1,95 @@
+//! Numeric helpers shared by the inversion routines.
+
+/// Find a root of `f` in `[lo, hi]` (order of the bounds does not matter).
+///
+/// The interval must bracket a sign change. Each iteration tries a Newton step
+/// with a central-difference derivative and falls back to bisection whenever
+/// that step would leave the current bracket, so convergence is guaranteed for
+/// continuous `f` while smooth functions converge quadratically.
+///
+/// Returns `None` if the bounds do not bracket a root, `f` produces a
+/// non-finite value, or the bracket does not shrink below `tol` within
+/// `max_iter` iterations.
+pub(crate) fn solve_1d<F: Fn(f64) -> f64>(
+    f: F,
+    lo: f64,
+    hi: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Option<f64> {
+    let (mut a, mut b) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+    let fa0 = f(a);
+    let fb0 = f(b);
+    if !fa0.is_finite() || !fb0.is_finite() {
+        return None;
+    }
+    if fa0 == 0.0 {
+        return Some(a);
+    }
+    if fb0 == 0.0 {
+        return Some(b);
+    }
+    if fa0.signum() == fb0.signum() {
+        return None;
+    }
+
+    let mut fa = fa0;
+    let mut x = 0.5 * (a + b);
+    for _ in 0..max_iter {
+        let fx = f(x);
+        if !fx.is_finite() {
+            return None;
+        }
+        if fx == 0.0 {
+            return Some(x);
+        }
+
+        // Keep the root bracketed.
+        if fx.signum() == fa.signum() {
+            a = x;
+            fa = fx;
+        } else {
+            b = x;
+        }
+        if (b - a) < tol {
+            return Some(0.5 * (a + b));
+        }
+
+        // Newton step; fall back to bisection when it is unusable.
+        let h = 1e-7 * x.abs().max(1.0);
+        let dfdx = (f(x + h) - f(x - h)) / (2.0 * h);
+        let newton = x - fx / dfdx;
+        let x_new = if dfdx.is_finite() && dfdx != 0.0 && newton > a && newton < b {
+            newton
+        } else {
+            0.5 * (a + b)
+        };
+
+        if (x_new - x).abs() < tol {
+            return Some(x_new);
+        }
+        x = x_new;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_root_of_monotone_function() {
+        let root = solve_1d(|x| x * x * x - 2.0, 0.0, 3.0, 1e-12, 100).unwrap();
+        assert!((root - 2f64.powf(1.0 / 3.0)).abs() < 1e-10);
+
+        // Reversed bounds are accepted.
+        let root = solve_1d(|x| 5.0 - x, 10.0, 0.0, 1e-12, 100).unwrap();
+        assert!((root - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn returns_none_without_root_in_range() {
+        assert!(solve_1d(|x| x * x + 1.0, -2.0, 2.0, 1e-12, 100).is_none());
+        assert!(solve_1d(|x| x - 10.0, 0.0, 5.0, 1e-12, 100).is_none());
+    }
+}
@@ -0,0 +1,81 @@
+//! Mixing of two samples by volume.
+
+use crate::models::{Assumptions, Inputs};
+use crate::numeric::solve_1d;
+use crate::salinity::calculator::{SUMMARY_MAX_ITER, SUMMARY_TOL, iterate_sp, mass_budget};
+
+/// Mix `fraction_a` (by volume, clamped to 0..=1) of `a` with the remainder
+/// of `b`.
+///
+/// Concentrations (mg/L, µmol/kg) and alkalinity are volume-weighted, which
+/// neglects the small volume change on mixing. An optional field is only
+/// mixed when both samples supply it and is `None` otherwise, so give the
+/// diluent explicit zeros (e.g. `cl: Some(0.0)`) for pure water. Both
+/// samples are undiluted first (`Inputs::undiluted`). Custom ions of both
+/// samples are kept, each weighted by its sample's fraction. pH does not mix
+/// linearly and is dropped, as are per-sample `t_c`/`p_dbar` (the blend's
+/// conditions come from the assumptions).
+pub fn mix(a: &Inputs, b: &Inputs, fraction_a: f64) -> Inputs {
+    let (a, b) = (&a.undiluted(), &b.undiluted());
+    let fa = fraction_a.clamp(0.0, 1.0);
+    let lerp = |x: f64, y: f64| fa * x + (1.0 - fa) * y;
+    let lerp_opt = |x: Option<f64>, y: Option<f64>| Some(lerp(x?, y?));
+
+    Inputs {
+        na: lerp(a.na, b.na),
+        ca: lerp(a.ca, b.ca),
+        mg: lerp(a.mg, b.mg),
+        k: lerp(a.k, b.k),
+        sr: lerp(a.sr, b.sr),
+        br: lerp(a.br, b.br),
+        cl: lerp_opt(a.cl, b.cl),
+        f: lerp_opt(a.f, b.f),
+        s: lerp(a.s, b.s),
+        so4: lerp_opt(a.so4, b.so4),
+        b: lerp(a.b, b.b),
+        alk_dkh: lerp_opt(a.alk_dkh, b.alk_dkh),
+        si_umol_kg: lerp_opt(a.si_umol_kg, b.si_umol_kg),
+        no3_umol_kg: lerp_opt(a.no3_umol_kg, b.no3_umol_kg),
+        po4_umol_kg: lerp_opt(a.po4_umol_kg, b.po4_umol_kg),
+        alk_ppm_caco3: lerp_opt(a.alk_ppm_caco3, b.alk_ppm_caco3),
+        gh_dgh: lerp_opt(a.gh_dgh, b.gh_dgh),
+        dilution_factor: None,
+        ph: None,
+        t_c: None,
+        p_dbar: None,
+        custom: match (&a.custom, &b.custom) {
+            (None, None) => None,
+            _ => Some(
+                a.custom_ions()
+                    .map(|ion| ion.scaled(fa))
+                    .chain(b.custom_ions().map(|ion| ion.scaled(1.0 - fa)))
+                    .collect(),
+            ),
+        },
+    }
+}
+
+/// Volume fraction of `stock` in a `mix` with `diluent` whose SP equals
+/// `target_sp`.
+///
+/// Returns `None` when `target_sp` lies outside the SP range spanned by the
+/// two samples.
+pub fn blend_ratio_for_target_sp(
+    stock: &Inputs,
+    diluent: &Inputs,
+    target_sp: f64,
+    ass: &Assumptions,
+) -> Option<f64> {
+    let ass = ass.clone().normalized();
+    let sp_of = |fraction: f64| {
+        let blend = mix(stock, diluent, fraction);
+        iterate_sp(
+            &mass_budget(&blend, &ass, None),
+            &ass,
+            SUMMARY_MAX_ITER,
+            SUMMARY_TOL,
+        )
+        .sp
+    };
+    solve_1d(|x| sp_of(x) - target_sp, 0.0, 1.0, 1e-9, 100)
+}
@@ -1,13 +1,28 @@
-use crate::adapters::teos10::{ct_from_t, rho, sa_from_sp};
+use crate::adapters::brine::rho_brine;
+use crate::adapters::teos10::{
+    SP_MAX_PSS78, SP_MIN_PSS78, T_MIN_C, ct_freezing, ct_from_t, kappa, rho, sa_from_sp,
+};
+use crate::chemistry::alkalinity::{AlkalinityModel, FixedFraction};
+use crate::chemistry::chloride::{Blended, ChlorideEstimator, EstimationContext};
+use crate::chemistry::contamination::diagnose_contamination;
 use crate::chemistry::*;
-use crate::models::{Assumptions, Inputs};
-use serde::Serialize;
+use crate::models::{Assumptions, ConcentrationBasis, EosKind, Inputs};
+use crate::numeric::solve_1d;
+use crate::warning::Warning;
+use serde::{Deserialize, Deserializer, Serialize};
 
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 #[cfg(not(feature = "std"))]
 use alloc::vec;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+/// Iteration limit used by `compute_summary` and `compute_full`.
+pub const SUMMARY_MAX_ITER: usize = 30;
+/// SP convergence tolerance used by `compute_summary` and `compute_full`.
+pub const SUMMARY_TOL: f64 = 1e-8;
+
 /// Result of a salinity calculation.
 ///
 /// This enum expresses the two possible return shapes from the calculation
@@ -16,7 +31,8 @@ use alloc::vec::Vec;
 /// - `Simple(f64)` — a single practical salinity (SP) value.
 /// - `Detailed(DetailedResult)` — a richer result with density and per-ion
 ///   component breakdowns.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CalcResult {
     /// A single SP value (practical salinity).
     Simple(f64),
@@ -35,25 +51,87 @@ pub enum CalcResult {
 ///
 /// `norm_factor` is the multiplicative factor used to normalize component
 /// values to SP = 35.
-#[derive(Debug)]
+///
+/// `mgkg_sa_ref` is mg/kg normalized to Absolute Salinity `SR_REF`
+/// (35.16504 g/kg) with `norm_factor_sa = SR_REF / SA`, where SA includes the
+/// nutrient anomaly. Without nutrients it coincides with `mgkg_sp35` at the
+/// default `salinity_norm`.
+///
+/// `estimated` runs parallel to the tables and is `true` for species whose
+/// value was estimated or taken from an assumption default rather than
+/// measured (chloride estimate, default fluoride, assumed alkalinity).
+///
+/// Deserializing maps the names back onto the crate's `'static` names
+/// (`chemistry::species_name`) and rejects unknown species.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Components {
+    #[serde(deserialize_with = "species_table")]
     pub mg_l: Vec<(&'static str, f64)>,
+    #[serde(deserialize_with = "species_table")]
     pub mgkg: Vec<(&'static str, f64)>,
+    #[serde(deserialize_with = "species_table")]
     pub mg_l_sp35: Vec<(&'static str, f64)>,
+    #[serde(deserialize_with = "species_table")]
     pub mgkg_sp35: Vec<(&'static str, f64)>,
     pub norm_factor: f64,
+    #[serde(deserialize_with = "species_table")]
+    pub mgkg_sa_ref: Vec<(&'static str, f64)>,
+    pub norm_factor_sa: f64,
+    #[serde(deserialize_with = "species_table")]
+    pub estimated: Vec<(&'static str, bool)>,
+}
+
+/// Deserialize a `(name, value)` table, interning names via `species_name`.
+fn species_table<'de, D, T>(deserializer: D) -> Result<Vec<(&'static str, T)>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    use serde::de::{Error, Unexpected};
+
+    Vec::<(String, T)>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, value)| {
+            species_name(&name)
+                .map(|name| (name, value))
+                .ok_or_else(|| Error::invalid_value(Unexpected::Str(&name), &"a known species"))
+        })
+        .collect()
+}
+
+impl Components {
+    /// Whether the named species was estimated/defaulted rather than measured.
+    /// Unknown names return `false`.
+    pub fn is_estimated(&self, species: &str) -> bool {
+        self.estimated
+            .iter()
+            .any(|(name, est)| *name == species && *est)
+    }
 }
 
 /// A detailed result returned when the caller requests component output.
 ///
 /// - `sp`: practical salinity (rounded in the caller before return)
+/// - `sr`: converged salinity ratio (Reference Salinity, g/kg), unrounded
 /// - `rho_kg_m3`: in-situ density in kg/m^3
 /// - `components`: per-ion concentration tables and normalization factor
-#[derive(Debug)]
+/// - `so4_mg_l`: sulfate (mg/L as SO4) entering the mass and charge balance
+/// - `so4_source`: whether `so4_mg_l` was converted from `s` or given directly
+/// - `ref_sum_gkg`: reference mass (g/kg at SP = 35) the measured sum is
+///   compared against, see `effective_ref_sum`
+/// - `iteration_history`: unrounded SP after each solver iteration, ending at
+///   the converged value; only with `Assumptions::record_history`
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DetailedResult {
     pub sp: f64,
+    pub sr: f64,
     pub rho_kg_m3: f64,
     pub components: Components,
+    pub so4_mg_l: f64,
+    pub so4_source: SulfateSource,
+    pub ref_sum_gkg: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iteration_history: Option<Vec<f64>>,
 }
 
 /// Lightweight summary returned for higher-level callers (e.g. UI or API).
@@ -61,16 +139,28 @@ pub struct DetailedResult {
 /// Fields:
 /// - `sp`: practical salinity
 /// - `sa`: absolute salinity (g/kg)
+/// - `sa_anomaly_g_kg`: absolute salinity anomaly SA − SR (g/kg) from the
+///   nutrient correction plus `Assumptions::delta_sa`; `0.0` when neither is
+///   supplied
 /// - `density_kg_per_m3`: in-situ density at the sample conditions
 /// - `sg_20_20`: specific gravity at 20°C/20°C reference (unitless)
 /// - `sg_25_25`: specific gravity at 25°C/25°C reference (unitless)
+/// - `normalized_sp`: SP after normalization to `Assumptions::salinity_norm`
+/// - `normalized_ion_table`: per-ion mg/L scaled to `salinity_norm`
+/// - `sa_composition_g_kg`: measured solute mass per kg of sample (g/kg, see
+///   `sa_from_composition`); only with `Assumptions::report_composition_sa`
 #[derive(Serialize, Debug, Clone)]
 pub struct CalculationSummary {
     pub sp: f64,
     pub sa: f64,
+    pub sa_anomaly_g_kg: f64,
     pub density_kg_per_m3: f64,
     pub sg_20_20: f64,
     pub sg_25_25: f64,
+    pub normalized_sp: f64,
+    pub normalized_ion_table: Vec<(&'static str, f64)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sa_composition_g_kg: Option<f64>,
 }
 
 /// Compute practical salinity (SP) iteratively from an `Inputs` structure.
@@ -94,48 +184,491 @@ pub fn calc_salinity_sp_iterative(
     max_iter: usize,
     tol: f64,
 ) -> CalcResult {
-    // Partition boron between boric acid and borate based on assumptions.
-    let (n_boric, n_borate) = boron_partition(
-        inp.b,
-        if ass.assume_borate {
-            ass.borate_fraction.unwrap_or(BORATE_FRACTION_DEFAULT)
+    calc_salinity_sp_iterative_with_estimator(inp, ass, max_iter, tol, None)
+}
+
+/// Same as `calc_salinity_sp_iterative`, with a caller-supplied chloride model.
+///
+/// `cl_estimator` is consulted only when `inp.cl` is missing or non-positive;
+/// `None` uses the default `Blended` strategy (charge balance + ratios).
+pub fn calc_salinity_sp_iterative_with_estimator(
+    inp: &Inputs,
+    ass: &Assumptions,
+    max_iter: usize,
+    tol: f64,
+    cl_estimator: Option<&dyn ChlorideEstimator>,
+) -> CalcResult {
+    calc_salinity_sp_iterative_with_models(inp, ass, max_iter, tol, cl_estimator, None)
+}
+
+/// Same as `calc_salinity_sp_iterative_with_estimator`, with a caller-supplied
+/// alkalinity speciation as well.
+///
+/// `alk_model` splits the alkalinity into HCO3-, CO3^2- and OH- (visible with
+/// `ass.expand_alk_species`) at the solved SP; `None` uses the default
+/// `FixedFraction`.
+pub fn calc_salinity_sp_iterative_with_models(
+    inp: &Inputs,
+    ass: &Assumptions,
+    max_iter: usize,
+    tol: f64,
+    cl_estimator: Option<&dyn ChlorideEstimator>,
+    alk_model: Option<&dyn AlkalinityModel>,
+) -> CalcResult {
+    let ass = &ass.at_sample_conditions(inp);
+    let mut budget = mass_budget_with(inp, ass, cl_estimator, alk_model, ass.salinity_norm);
+    let iteration = iterate_sp(&budget, ass, max_iter, tol);
+    // The split carries the same charge and mass whatever the salinity, so SP
+    // is unaffected; speciate again at the solved SP for the reported rows.
+    if alk_model.is_some() {
+        budget = mass_budget_with(inp, ass, cl_estimator, alk_model, iteration.sp);
+    }
+
+    // If the caller did not request component output, return a compact value.
+    if !ass.return_components {
+        return CalcResult::Simple(round_to(iteration.sp, ass.precision.sp as i32));
+    }
+    CalcResult::Detailed(detailed_result(inp, ass, &budget, iteration))
+}
+
+/// Like `calc_salinity_sp_iterative`, but always returns the detailed result
+/// (regardless of `ass.return_components`); its `sp` equals the `Simple`
+/// value for the same arguments.
+pub fn calc_all(inp: &Inputs, ass: &Assumptions, max_iter: usize, tol: f64) -> DetailedResult {
+    let ass = &ass.at_sample_conditions(inp);
+    let budget = mass_budget(inp, ass, None);
+    detailed_result(inp, ass, &budget, iterate_sp(&budget, ass, max_iter, tol))
+}
+
+/// Density and component tables at the converged `sp`/`sa` (unrounded) of
+/// `budget`, with `sp` rounded for output.
+fn detailed_result(
+    inp: &Inputs,
+    ass: &Assumptions,
+    budget: &MassBudget,
+    iteration: Iteration,
+) -> DetailedResult {
+    let Iteration {
+        sp, sa, history, ..
+    } = iteration;
+    // Recompute final density at the converged SA for output.
+    let rho_final = conversion_rho(sa, ass);
+    let kg_per_l = rho_final / 1000.0;
+
+    let mg_l_table = in_species_order(
+        if ass.expand_alk_species {
+            budget.mg_l_table_with_alk_species()
         } else {
-            0.0
+            budget.mg_l_table()
+        },
+        ass.species_order.as_deref(),
+    );
+
+    let cl_estimated = inp.cl.filter(|&c| c > 0.0).is_none();
+    let f_estimated = inp.f.is_none();
+    let alk_estimated = inp.measured_alk_dkh().is_none();
+    let estimated: Vec<(&str, bool)> = mg_l_table
+        .iter()
+        .map(|(k, _)| {
+            let est = match *k {
+                "Cl-" => cl_estimated,
+                "F-" => f_estimated,
+                "Alk." | "HCO3-" | "CO3^2-" | "OH-" => alk_estimated,
+                _ => false,
+            };
+            (*k, est)
+        })
+        .collect();
+
+    let mgkg_table: Vec<(&str, f64)> = mg_l_table
+        .iter()
+        .map(|(k, v)| (*k, *v / kg_per_l))
+        .collect();
+
+    // A zero panel (SP = 0) has nothing to normalize; report zeroed tables
+    // rather than dividing by zero.
+    let norm_factor = if sp > TINY {
+        ass.salinity_norm / sp
+    } else {
+        0.0
+    };
+    let mg_l_norm: Vec<(&str, f64)> = mg_l_table
+        .iter()
+        .map(|(k, v)| (*k, *v * norm_factor))
+        .collect();
+    let mgkg_norm: Vec<(&str, f64)> = mgkg_table
+        .iter()
+        .map(|(k, v)| (*k, *v * norm_factor))
+        .collect();
+
+    let sa_abs = sa + sa_anomaly(inp, ass);
+    let norm_factor_sa = if sa_abs > TINY { SR_REF / sa_abs } else { 0.0 };
+    let mgkg_sa_ref: Vec<(&str, f64)> = mgkg_table
+        .iter()
+        .map(|(k, v)| (*k, *v * norm_factor_sa))
+        .collect();
+
+    DetailedResult {
+        sp: round_to(sp, ass.precision.sp as i32),
+        sr: sa,
+        rho_kg_m3: rho_final,
+        components: Components {
+            mg_l: mg_l_table,
+            mgkg: mgkg_table,
+            mg_l_sp35: mg_l_norm,
+            mgkg_sp35: mgkg_norm,
+            norm_factor,
+            mgkg_sa_ref,
+            norm_factor_sa,
+            estimated,
         },
+        so4_mg_l: budget.so4_mg_l,
+        so4_source: budget.so4_source,
+        ref_sum_gkg: budget.sum_ref_gkg,
+        iteration_history: history,
+    }
+}
+
+/// `table` with the species named in `order` moved to the front, in that
+/// order; the remaining rows keep their relative order.
+fn in_species_order(
+    mut table: Vec<(&'static str, f64)>,
+    order: Option<&[String]>,
+) -> Vec<(&'static str, f64)> {
+    let Some(order) = order else {
+        return table;
+    };
+    let mut ordered = Vec::with_capacity(table.len());
+    for name in order {
+        if let Some(i) = table.iter().position(|(k, _)| *k == name.as_str()) {
+            ordered.push(table.remove(i));
+        }
+    }
+    ordered.extend(table);
+    ordered
+}
+
+/// SA anomaly (g/kg): the nutrient correction from the optional nutrient
+/// inputs plus the user-supplied `ass.delta_sa`.
+fn sa_anomaly(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let inp = inp.undiluted();
+    sa_nutrient_correction(
+        inp.si_umol_kg.unwrap_or(0.0),
+        inp.no3_umol_kg.unwrap_or(0.0),
+        inp.po4_umol_kg.unwrap_or(0.0),
+    ) + ass.delta_sa.unwrap_or(0.0)
+}
+
+/// Fraction of boron treated as borate: 0 unless `assume_borate` is set.
+fn effective_borate_fraction(ass: &Assumptions) -> f64 {
+    if ass.assume_borate {
+        ass.borate_fraction.unwrap_or(BORATE_FRACTION_DEFAULT)
+    } else {
+        0.0
+    }
+}
+
+/// `inp` undiluted, in mg/L (per `ass.input_units`) and with GH apportioned
+/// into Ca/Mg.
+pub(crate) fn effective_inputs(inp: &Inputs, ass: &Assumptions) -> Inputs {
+    let ca_per_mg = ass.gh_ca_mg_ratio.unwrap_or(REF_MMOL_CA / REF_MMOL_MG);
+    inp.undiluted()
+        .to_mg_per_l(ass.input_units)
+        .with_gh_apportioned(ca_per_mg)
+}
+
+/// Per-species mass budget (g/L) entering the SP iteration, plus the
+/// reference sum (g/kg at SP = 35) it is matched against.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MassBudget {
+    pub na: f64,
+    pub ca: f64,
+    pub mg: f64,
+    pub k: f64,
+    pub sr: f64,
+    pub br: f64,
+    pub so4: f64,
+    pub f: f64,
+    pub alk: f64,
+    /// Carbonate-system species behind `alk` (mol/L).
+    pub n_hco3: f64,
+    pub n_co3: f64,
+    pub n_oh: f64,
+    pub boric: f64,
+    pub borate: f64,
+    pub cl: f64,
+    /// All `Inputs::custom` ions together.
+    pub custom: f64,
+    pub so4_mg_l: f64,
+    pub so4_source: SulfateSource,
+    pub sum_ref_gkg: f64,
+}
+
+impl MassBudget {
+    /// Sum of all dissolved species in g/L.
+    pub fn total_g_l(&self) -> f64 {
+        [
+            self.na,
+            self.ca,
+            self.mg,
+            self.k,
+            self.sr,
+            self.br,
+            self.f,
+            self.so4,
+            self.boric,
+            self.borate,
+            self.alk,
+            self.cl,
+            self.custom,
+        ]
+        .into_iter()
+        .sum()
+    }
+
+    /// Species table in mg/L, in the order reported by `Components`; a lumped
+    /// `Custom` row follows when the panel has custom ions.
+    pub fn mg_l_table(&self) -> Vec<(&'static str, f64)> {
+        let mut table = vec![
+            ("Na+", self.na * 1000.0),
+            ("Ca2+", self.ca * 1000.0),
+            ("Mg2+", self.mg * 1000.0),
+            ("K+", self.k * 1000.0),
+            ("Sr2+", self.sr * 1000.0),
+            ("Br-", self.br * 1000.0),
+            ("SO4^2-", self.so4 * 1000.0),
+            ("F-", self.f * 1000.0),
+            ("Alk.", self.alk * 1000.0),
+            ("B(OH)3", self.boric * 1000.0),
+            ("B(OH)4-", self.borate * 1000.0),
+            ("Cl-", self.cl * 1000.0),
+        ];
+        if self.custom > 0.0 {
+            table.push(("Custom", self.custom * 1000.0));
+        }
+        table
+    }
+
+    /// `mg_l_table` with the lumped `Alk.` row replaced by HCO3-, CO3^2- and
+    /// OH- as ion masses (mg/L). These rows are the species' own masses, not
+    /// the CaCO3-equivalent mass of `Alk.`, so they do not sum to it.
+    pub fn mg_l_table_with_alk_species(&self) -> Vec<(&'static str, f64)> {
+        let mut table = self.mg_l_table();
+        if let Some(i) = table.iter().position(|(k, _)| *k == "Alk.") {
+            table.splice(
+                i..=i,
+                [
+                    ("HCO3-", self.n_hco3 * M_HCO3 * 1000.0),
+                    ("CO3^2-", self.n_co3 * M_CO3 * 1000.0),
+                    ("OH-", self.n_oh * M_OH * 1000.0),
+                ],
+            );
+        }
+        table
+    }
+}
+
+/// Build the mass budget for `inp` under `ass`, estimating chloride with
+/// `cl_estimator` (default `Blended`) when it is missing. Concentrations are
+/// first converted to mg/L according to `ass.input_units`, and `gh_dgh` is
+/// apportioned into Ca/Mg.
+pub(crate) fn mass_budget(
+    inp: &Inputs,
+    ass: &Assumptions,
+    cl_estimator: Option<&dyn ChlorideEstimator>,
+) -> MassBudget {
+    mass_budget_with(inp, ass, cl_estimator, None, ass.salinity_norm)
+}
+
+/// `mass_budget`, splitting alkalinity with `alk_model` (default
+/// `FixedFraction`) at `ass.temp` and salinity `speciation_sp`.
+pub(crate) fn mass_budget_with(
+    inp: &Inputs,
+    ass: &Assumptions,
+    cl_estimator: Option<&dyn ChlorideEstimator>,
+    alk_model: Option<&dyn AlkalinityModel>,
+    speciation_sp: f64,
+) -> MassBudget {
+    match ass.input_basis {
+        ConcentrationBasis::PerLiter => {
+            mass_budget_per_l(inp, ass, cl_estimator, alk_model, speciation_sp)
+        }
+        ConcentrationBasis::PerKilogram => {
+            mass_budget_per_kg(inp, ass, cl_estimator, alk_model, speciation_sp)
+        }
+    }
+}
+
+/// Maximum density updates when converting a per-kilogram panel.
+const PER_KG_MAX_ITER: usize = 20;
+/// Convergence tolerance (kg/L) of the per-kilogram density.
+const PER_KG_TOL: f64 = 1e-12;
+
+/// Mass budget of a per-kilogram panel: the ions are multiplied by the
+/// in-situ density (kg/L), starting from SP = 35 seawater, and the density is
+/// re-evaluated at the resulting SA until it settles.
+fn mass_budget_per_kg(
+    inp: &Inputs,
+    ass: &Assumptions,
+    cl_estimator: Option<&dyn ChlorideEstimator>,
+    alk_model: Option<&dyn AlkalinityModel>,
+    speciation_sp: f64,
+) -> MassBudget {
+    let mut kg_per_l = conversion_rho(SR_REF, ass) / 1000.0;
+    let mut budget = mass_budget_per_l(
+        &ions_scaled(inp, kg_per_l),
+        ass,
+        cl_estimator,
+        alk_model,
+        speciation_sp,
     );
+    for _ in 0..PER_KG_MAX_ITER {
+        let sa = iterate_sp(&budget, ass, SUMMARY_MAX_ITER, SUMMARY_TOL).sa;
+        let next = conversion_rho(sa, ass) / 1000.0;
+        if !next.is_finite() || (next - kg_per_l).abs() < PER_KG_TOL {
+            break;
+        }
+        kg_per_l = next;
+        budget = mass_budget_per_l(
+            &ions_scaled(inp, kg_per_l),
+            ass,
+            cl_estimator,
+            alk_model,
+            speciation_sp,
+        );
+    }
+    budget
+}
+
+/// `inp` with the ion concentrations covered by `ConcentrationUnit`
+/// multiplied by `factor`.
+fn ions_scaled(inp: &Inputs, factor: f64) -> Inputs {
+    Inputs {
+        na: inp.na * factor,
+        ca: inp.ca * factor,
+        mg: inp.mg * factor,
+        k: inp.k * factor,
+        sr: inp.sr * factor,
+        br: inp.br * factor,
+        cl: inp.cl.map(|v| v * factor),
+        f: inp.f.map(|v| v * factor),
+        s: inp.s * factor,
+        so4: inp.so4.map(|v| v * factor),
+        b: inp.b * factor,
+        custom: inp.custom_scaled(factor),
+        ..inp.clone()
+    }
+}
+
+/// `mass_budget_with` for a per-litre panel.
+fn mass_budget_per_l(
+    inp: &Inputs,
+    ass: &Assumptions,
+    cl_estimator: Option<&dyn ChlorideEstimator>,
+    alk_model: Option<&dyn AlkalinityModel>,
+    speciation_sp: f64,
+) -> MassBudget {
+    let inp = &effective_inputs(inp, ass);
+    // An empty panel gets no assumed fluoride or alkalinity either, so the
+    // budget is zero and the solver settles on SP = 0.
+    let empty = is_zero_panel(inp);
+
+    // Partition boron between boric acid and borate based on assumptions.
+    let (n_boric, n_borate) = boron_partition(inp.b, effective_borate_fraction(ass));
 
     // Convert alkalinity (DKH or mg per meq) into species and total alkalinity
     // in mg/L for the mass-balance. The tuple contains derived species and
     // the equivalent alkalinity in mg/L used directly below.
-    let alk_dkh_eff = inp.alk_dkh.or(ass.alkalinity).unwrap_or(0.0);
-    let (n_hco3, n_co3, n_oh, alk_mg_l) = alk_species_from_dkh(alk_dkh_eff, ass.alk_mg_per_meq);
+    // Measured alkalinity (dKH, then ppm CaCO3) takes precedence over the
+    // assumed default.
+    let alk_dkh_eff = inp
+        .measured_alk_dkh()
+        .or(ass.alkalinity)
+        .filter(|_| !empty)
+        .unwrap_or(0.0);
+    // Same mg/meq as the reference alkalinity mass in `sum_ref_gkg`.
+    let (_, _, _, alk_mg_l) = alk_species_from_dkh(alk_dkh_eff, Some(alk_mg_per_meq(ass)));
+    let (n_hco3, n_co3, n_oh) =
+        alk_model
+            .unwrap_or(&FixedFraction)
+            .speciate(alk_dkh_eff, ass.temp, speciation_sp, inp.ph);
 
-    // Chloride: use provided value if positive, otherwise estimate using a
-    // blended strategy (charge balance + ratio constraints) for robustness.
+    let default_f_mg_l = if empty { 0.0 } else { ass.default_f_mg_l };
+    let f_mg_l = fluoride_mg_l(inp, default_f_mg_l);
+
+    // Chloride: use provided value if positive, otherwise estimate using the
+    // supplied estimator (default: blended charge balance + ratio constraints).
     let cl_mg_l = inp.cl.filter(|&c| c > 0.0).unwrap_or_else(|| {
-        estimate_cl_mg_l(inp, ass.default_f_mg_l, n_borate, n_hco3, n_co3, n_oh)
+        let ctx = EstimationContext {
+            default_f_mg_l,
+            n_borate,
+            n_hco3,
+            n_co3,
+            n_oh,
+            temp_c: ass.temp,
+            salinity: ass.salinity_norm,
+        };
+        cl_estimator.unwrap_or(&Blended).estimate(inp, &ctx)
     });
 
-    let f_mg_l = inp.f.unwrap_or(ass.default_f_mg_l);
-    let g_l_na = inp.na.max(0.0) / 1000.0;
-    let g_l_ca = inp.ca.max(0.0) / 1000.0;
-    let g_l_mg = inp.mg.max(0.0) / 1000.0;
-    let g_l_k = inp.k.max(0.0) / 1000.0;
-    let g_l_sr = inp.sr.max(0.0) / 1000.0;
-    let g_l_br = inp.br.max(0.0) / 1000.0;
-    let g_l_f = f_mg_l.max(0.0) / 1000.0;
-    let g_l_so4 = ((inp.s / M_S) * M_SO4 / 1000.0).max(0.0);
-    let g_l_boric = n_boric * M_BORIC;
-    let g_l_borate = n_borate * M_BORATE;
-    let g_l_alk = alk_mg_l / 1000.0;
-    let g_l_cl = cl_mg_l.max(0.0) / 1000.0;
-
-    let sum_ref_gkg = ref_sum_with_boron_species_and_ref_alk(
-        ass.ref_alk_dkh,
-        ass.assume_borate,
-        ass.borate_fraction,
-        ass.alk_mg_per_meq,
-    );
+    let (so4_mg_l, so4_source) = sulfate_mg_l(inp);
+
+    MassBudget {
+        na: inp.na.max(0.0) / 1000.0,
+        ca: inp.ca.max(0.0) / 1000.0,
+        mg: inp.mg.max(0.0) / 1000.0,
+        k: inp.k.max(0.0) / 1000.0,
+        sr: inp.sr.max(0.0) / 1000.0,
+        br: inp.br.max(0.0) / 1000.0,
+        so4: (so4_mg_l / 1000.0).max(0.0),
+        f: f_mg_l / 1000.0,
+        alk: alk_mg_l / 1000.0,
+        n_hco3,
+        n_co3,
+        n_oh,
+        boric: n_boric * M_BORIC,
+        borate: n_borate * M_BORATE,
+        cl: cl_mg_l.max(0.0) / 1000.0,
+        custom: custom_mass_mg_l(inp) / 1000.0,
+        so4_mg_l,
+        so4_source,
+        sum_ref_gkg: ref_sum_gkg(ass),
+    }
+}
+
+/// Unrounded outcome of the SP fixed-point iteration.
+#[derive(Clone, Debug)]
+pub(crate) struct Iteration {
+    pub sp: f64,
+    pub sa: f64,
+    pub iterations: usize,
+    pub converged: bool,
+    /// SP after each update, only with `Assumptions::record_history`.
+    pub history: Option<Vec<f64>>,
+}
+
+/// Run the SP fixed-point iteration on a prepared mass budget.
+///
+/// With `ass.fixed_sp` set the iteration is skipped and that SP is returned
+/// as-is (reported as converged after zero iterations, with an empty
+/// history).
+pub(crate) fn iterate_sp(
+    budget: &MassBudget,
+    ass: &Assumptions,
+    max_iter: usize,
+    tol: f64,
+) -> Iteration {
+    if let Some(sp) = ass.fixed_sp {
+        return Iteration {
+            sp,
+            sa: sp * SR_REF / 35.0,
+            iterations: 0,
+            converged: true,
+            history: ass.record_history.then(Vec::new),
+        };
+    }
+
+    let total_g_l = budget.total_g_l();
 
     // Start the iteration from a nominal SP = 35 and update until convergence.
     // The iteration adjusts the salinity ratio so that the measured sum of
@@ -143,91 +676,45 @@ pub fn calc_salinity_sp_iterative(
     // scaled by that salinity ratio.
     let mut sp = 35.0;
     let mut sa = sp * (SR_REF / 35.0);
+    let mut iterations = 0;
+    let mut converged = false;
+    let mut history = ass.record_history.then(Vec::new);
     for _ in 0..max_iter {
-        // Compute conservative temperature and density at current SA.
-        let ct = ct_from_t(sa, ass.temp, ass.pressure_dbar);
-        let rho_val = rho(sa, ct, ass.pressure_dbar);
-        let kg_per_l = rho_val / 1000.0;
-
-        // Sum the provided mass contributions (g/L) and convert to g/kg by
-        // dividing by the in-situ kg/L.
-        let sum_meas_gkg: f64 = [
-            g_l_na, g_l_ca, g_l_mg, g_l_k, g_l_sr, g_l_br, g_l_f, g_l_so4, g_l_boric, g_l_borate,
-            g_l_alk, g_l_cl,
-        ]
-        .into_iter()
-        .sum::<f64>()
-            / kg_per_l;
+        iterations += 1;
+        // Compute the in-situ density at current SA.
+        let kg_per_l = conversion_rho(sa, ass) / 1000.0;
+
+        // Convert the measured mass contributions (g/L) to g/kg by dividing
+        // by the in-situ kg/L.
+        let sum_meas_gkg = total_g_l / kg_per_l;
 
         // New salinity ratio (sr) is set by matching the measured sum to the
         // reference sum (with protection against division by tiny values).
-        let sr_new = SR_REF * (sum_meas_gkg / sum_ref_gkg.max(TINY));
+        let sr_new = SR_REF * (sum_meas_gkg / budget.sum_ref_gkg.max(TINY));
         let sp_new = 35.0 * sr_new / SR_REF;
         let sa_new = sr_new;
+        if let Some(history) = history.as_mut() {
+            history.push(sp_new);
+        }
 
         // Check for convergence on the practical salinity (SP).
         if (sp_new - sp).abs() < tol {
             sp = sp_new;
             sa = sa_new;
+            converged = true;
             break;
         }
         sp = sp_new;
         sa = sa_new;
     }
 
-    // If the caller did not request component output, return a compact value.
-    if !ass.return_components {
-        return CalcResult::Simple(round_to(sp, 4));
+    Iteration {
+        sp,
+        sa,
+        iterations,
+        converged,
+        history,
     }
-
-    // Recompute final density at the converged SA for output.
-    let rho_final = {
-        let ct = ct_from_t(sa, ass.temp, ass.pressure_dbar);
-        rho(sa, ct, ass.pressure_dbar)
-    };
-    let kg_per_l = rho_final / 1000.0;
-
-    let mg_l_table = vec![
-        ("Na+", g_l_na * 1000.0),
-        ("Ca2+", g_l_ca * 1000.0),
-        ("Mg2+", g_l_mg * 1000.0),
-        ("K+", g_l_k * 1000.0),
-        ("Sr2+", g_l_sr * 1000.0),
-        ("Br-", g_l_br * 1000.0),
-        ("SO4^2-", g_l_so4 * 1000.0),
-        ("F-", g_l_f * 1000.0),
-        ("Alk.", g_l_alk * 1000.0),
-        ("B(OH)3", g_l_boric * 1000.0),
-        ("B(OH)4-", g_l_borate * 1000.0),
-        ("Cl-", g_l_cl * 1000.0),
-    ];
-
-    let mgkg_table: Vec<(&str, f64)> = mg_l_table
-        .iter()
-        .map(|(k, v)| (*k, *v / kg_per_l))
-        .collect();
-
-    let norm_factor = ass.salinity_norm / sp.max(TINY);
-    let mg_l_norm: Vec<(&str, f64)> = mg_l_table
-        .iter()
-        .map(|(k, v)| (*k, *v * norm_factor))
-        .collect();
-    let mgkg_norm: Vec<(&str, f64)> = mgkg_table
-        .iter()
-        .map(|(k, v)| (*k, *v * norm_factor))
-        .collect();
-
-    CalcResult::Detailed(DetailedResult {
-        sp: round_to(sp, 4),
-        rho_kg_m3: rho_final,
-        components: Components {
-            mg_l: mg_l_table,
-            mgkg: mgkg_table,
-            mg_l_sp35: mg_l_norm,
-            mgkg_sp35: mgkg_norm,
-            norm_factor,
-        },
-    })
 }
 
 /// Compute practical salinity (SP) using TEOS-10 assumptions.
@@ -255,11 +742,126 @@ pub fn calc_salinity_sp_teos10(
     calc_salinity_sp_iterative(base_inp, &ass_norm, max_iter, tol)
 }
 
+/// SP attributable to alkalinity and boron species in the mass budget.
+///
+/// Returns SP(full budget) − SP(budget with `Alk.`, `B(OH)3` and `B(OH)4-`
+/// removed), both unrounded and against the same reference sum. Zero when
+/// the sample has neither alkalinity nor boron.
+pub fn nonconservative_salinity_contribution(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    let budget = mass_budget(inp, &ass, None);
+    let conservative = MassBudget {
+        alk: 0.0,
+        boric: 0.0,
+        borate: 0.0,
+        ..budget
+    };
+    let sp = |b: &MassBudget| iterate_sp(b, &ass, SUMMARY_MAX_ITER, SUMMARY_TOL).sp;
+    sp(&budget) - sp(&conservative)
+}
+
+/// Total dissolved solids (mg/L): the sum of every species in the solver's
+/// mass budget, including estimated chloride, default fluoride, alkalinity
+/// and boron species.
+pub fn tds_mg_l(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    mass_budget(inp, &ass, None).total_g_l() * 1000.0
+}
+
+/// Mass-balance closure (%): measured dissolved mass over the mass expected
+/// for standard seawater at the panel's major-ion scaling.
+///
+/// The measured total is the solver's mass budget without a chloride
+/// estimate (an estimated Cl⁻ is not a measurement); the expected total is
+/// the solver's reference sum scaled by `median_reference_scale`. Near 100 %
+/// the panel accounts for its salinity; well below means missing mass (e.g.
+/// unmeasured chloride), well above means extra mass. `NaN` for a panel
+/// without major ions.
+pub fn mass_closure_pct(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    let effective = effective_inputs(inp, &ass);
+    let budget = mass_budget(inp, &ass, None);
+    let cl_measured = effective.cl.is_some_and(|c| c > 0.0);
+    let measured_g_l = budget.total_g_l() - if cl_measured { 0.0 } else { budget.cl };
+    // mol/L per mmol/kg -> mmol/L per mmol/kg = kg/L times the salinity ratio.
+    let expected_g_l = budget.sum_ref_gkg * median_reference_scale(&effective) * 1000.0;
+    if expected_g_l > 0.0 {
+        100.0 * measured_g_l / expected_g_l
+    } else {
+        f64::NAN
+    }
+}
+
+/// Mass of dissolved salt (kg) in `volume_liters` of the sample, from
+/// `tds_mg_l`. Negative volumes are treated as zero.
+pub fn salt_mass_kg(inp: &Inputs, ass: &Assumptions, volume_liters: f64) -> f64 {
+    tds_mg_l(inp, ass) * volume_liters.max(0.0) / 1e6
+}
+
+/// Reference mass (g/kg) of SP = 35 seawater that the solver compares the
+/// measured sum against: the reference composition with boron as boric
+/// acid/borate and, with `ref_alk_dkh`, the reference alkalinity mass.
+///
+/// Normalizes `ass` first, so `rn_compat` (reference alkalinity 6.2 dKH)
+/// applies as in `compute_summary`; equals `DetailedResult::ref_sum_gkg` for
+/// normalized assumptions.
+pub fn effective_ref_sum(ass: &Assumptions) -> f64 {
+    ref_sum_gkg(&ass.clone().normalized())
+}
+
+/// `ref_sum_with_boron_species_and_ref_alk` for `ass` as given.
+fn ref_sum_gkg(ass: &Assumptions) -> f64 {
+    ref_sum_with_boron_species_and_ref_alk(
+        ass.ref_alk_dkh,
+        ass.assume_borate,
+        ass.borate_fraction,
+        Some(alk_mg_per_meq(ass)),
+    )
+}
+
+/// mg/meq for both the measured and the reference alkalinity mass.
+fn alk_mg_per_meq(ass: &Assumptions) -> f64 {
+    effective_alk_mg_per_meq(ass.alk_mg_per_meq)
+}
+
+/// Absolute Salinity (g/kg) as the sum of the measured solute masses per kg
+/// of sample, i.e. the solver's `sum_meas` at the converged density.
+///
+/// `sa_from_sp` instead maps SP onto the standard composition. Since the
+/// solver derives SP from this same sum, the two differ only by the constant
+/// `sum_ref / SR_REF` (≈0.5 %, alkalinity counted as CaCO3 equivalents)
+/// plus any SA anomaly — unless SP comes from elsewhere (`fixed_sp`, e.g. a
+/// conductivity reading), where a non-standard composition such as a
+/// Ca-enriched tank makes them diverge. Normalizes `ass` first.
+pub fn sa_from_composition(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    let budget = mass_budget(inp, &ass, None);
+    let sa = iterate_sp(&budget, &ass, SUMMARY_MAX_ITER, SUMMARY_TOL).sa;
+    budget.total_g_l() / (conversion_rho(sa, &ass) / 1000.0)
+}
+
+/// Converged salinity ratio `SR_REF * sum_meas / sum_ref` (g/kg) for `inp`.
+///
+/// This is the quantity the solver iterates on; SP follows as
+/// `35 * sr / SR_REF`. Uses the `compute_summary` iteration limits and
+/// normalizes `ass` first.
+pub fn salinity_ratio(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    iterate_sp(
+        &mass_budget(inp, &ass, None),
+        &ass,
+        SUMMARY_MAX_ITER,
+        SUMMARY_TOL,
+    )
+    .sa
+}
+
 /// Compute in-situ density (kg/m³) from Practical Salinity (SP).
 ///
 /// The function converts `sp` to absolute salinity (SA) via `sa_from_sp`,
 /// computes conservative temperature from the provided `Assumptions` and then
-/// calls the TEOS-10 `rho` routine. Units:
+/// calls the TEOS-10 `rho` routine (or the brine correlation when
+/// `ass.eos` is `BrineExtended`). Units:
 /// - `sp` is unitless (practical salinity)
 /// - `ass.temp` is °C, `ass.pressure_dbar` is in dbar
 /// - return value is density in kg/m³
@@ -267,18 +869,85 @@ pub fn calc_salinity_sp_teos10(
 /// This helper is convenient when callers only have SP and a set of
 /// environmental assumptions.
 pub fn rho_from_sp(sp: f64, ass: &Assumptions) -> f64 {
+    in_situ_rho(sa_from_sp(sp), ass)
+}
+
+/// In-situ density (kg/m³) at `sa` and the conditions in `ass`, using the
+/// equation of state selected by `ass.eos` and scaled for
+/// `ass.d2o_fraction`.
+fn in_situ_rho(sa: f64, ass: &Assumptions) -> f64 {
+    let rho_h2o = match ass.eos {
+        EosKind::Teos10 => {
+            let ct = ct_from_t(sa, ass.temp, ass.pressure_dbar);
+            rho(sa, ct, ass.pressure_dbar)
+        }
+        EosKind::BrineExtended => rho_brine(sa, ass.temp),
+    };
+    rho_h2o * d2o_density_factor(ass.d2o_fraction)
+}
+
+/// Density (kg/m³) the solver converts mg/L to mg/kg with:
+/// `ass.measured_density` when set, otherwise `in_situ_rho` at `sa`.
+fn conversion_rho(sa: f64, ass: &Assumptions) -> f64 {
+    ass.measured_density.unwrap_or_else(|| in_situ_rho(sa, ass))
+}
+
+/// Density of pure D2O relative to pure H2O near room temperature
+/// (1105.3 / 998.2 kg/m³ at 20 °C).
+pub const D2O_DENSITY_RATIO: f64 = 1.1073;
+
+/// Density multiplier for water containing a volume fraction `d2o` of D2O,
+/// assuming ideal mixing. `None` and non-finite fractions give 1; others are
+/// clamped to `[0, 1]`.
+pub fn d2o_density_factor(d2o: Option<f64>) -> f64 {
+    match d2o.filter(|f| f.is_finite()) {
+        Some(f) => 1.0 + f.clamp(0.0, 1.0) * (D2O_DENSITY_RATIO - 1.0),
+        None => 1.0,
+    }
+}
+
+/// Isentropic compressibility κ (1/Pa) from Practical Salinity (SP) at the
+/// temperature and pressure in `ass`.
+///
+/// Related to density and sound speed by c² = 1/(ρκ); the bulk modulus is
+/// 1/κ.
+pub fn compressibility(sp: f64, ass: &Assumptions) -> f64 {
     let sa = sa_from_sp(sp);
     let ct = ct_from_t(sa, ass.temp, ass.pressure_dbar);
-    rho(sa, ct, ass.pressure_dbar)
+    kappa(sa, ct, ass.pressure_dbar)
 }
 
+/// How far (K) a sample of Practical Salinity `sp` at the temperature and
+/// pressure in `ass` is above its freezing point: in-situ CT minus the
+/// freezing CT of air-saturated seawater (`ct_freezing`). Negative values
+/// mean supercooled water.
+pub fn freezing_margin(sp: f64, ass: &Assumptions) -> f64 {
+    let sa = sa_from_sp(sp);
+    ct_from_t(sa, ass.temp, ass.pressure_dbar) - ct_freezing(sa, ass.pressure_dbar, 1.0)
+}
+
+/// Invert `rho_from_sp`: find the Practical Salinity whose in-situ density at
+/// the temperature and pressure in `ass` equals `rho_kg_m3`.
+///
+/// The search covers SP in `[0, 120]` (fresh water to concentrated brines).
+/// Returns `None` when the density lies outside that range (e.g. below the
+/// pure-water density) or the density routine fails.
+pub fn sp_from_density(rho_kg_m3: f64, ass: &Assumptions) -> Option<f64> {
+    solve_1d(|sp| rho_from_sp(sp, ass) - rho_kg_m3, 0.0, 120.0, 1e-9, 100)
+}
+
+/// Smallest pure-water density (kg/m³) `specific_gravity` divides by; real
+/// values are ~1000, so anything below this is a failed density evaluation.
+pub const SG_PURE_WATER_RHO_EPSILON: f64 = 1e-6;
+
 /// Compute the specific gravity of a seawater sample relative to pure water.
 ///
 /// The specific gravity is defined here as the ratio rho(sw) / rho(pw) where
 /// `rho(sw)` is the density of seawater at the specified SP, temperature and
 /// pressure, and `rho(pw)` is the density of pure water at the same
-/// temperature and pressure. If the pure-water density routine returns zero
-/// (unexpected), the function conservatively returns `1.0`.
+/// temperature and pressure. If the pure-water density is non-finite or not
+/// above `SG_PURE_WATER_RHO_EPSILON` (a domain failure), the result is
+/// `NaN`; `compute_full` reports that as `Warning::OutOfDomain`.
 ///
 /// Parameters:
 /// - `sp`: practical salinity (unitless)
@@ -287,12 +956,65 @@ pub fn rho_from_sp(sp: f64, ass: &Assumptions) -> f64 {
 ///
 /// Returns the dimensionless specific gravity (unitless).
 pub fn specific_gravity(sp: f64, t_ref: f64, p_ref: f64) -> f64 {
+    specific_gravity_with_epsilon(sp, t_ref, p_ref, SG_PURE_WATER_RHO_EPSILON)
+}
+
+/// `specific_gravity` with an explicit guard `epsilon` (kg/m³) for the
+/// pure-water density.
+pub fn specific_gravity_with_epsilon(sp: f64, t_ref: f64, p_ref: f64, epsilon: f64) -> f64 {
     let sa = sa_from_sp(sp);
     let ct_sw = ct_from_t(sa, t_ref, p_ref);
     let rho_sw = rho(sa, ct_sw, p_ref);
     let ct_pw = ct_from_t(0.0, t_ref, p_ref);
     let rho_pw = rho(0.0, ct_pw, p_ref);
-    if rho_pw == 0.0 { 1.0 } else { rho_sw / rho_pw }
+    specific_gravity_from_densities(rho_sw, rho_pw, epsilon)
+}
+
+/// Ratio `rho_sw / rho_pw`, or `NaN` when `rho_pw` is non-finite or not
+/// above `epsilon` (kg/m³).
+pub fn specific_gravity_from_densities(rho_sw: f64, rho_pw: f64, epsilon: f64) -> f64 {
+    if rho_pw.is_finite() && rho_pw > epsilon {
+        rho_sw / rho_pw
+    } else {
+        f64::NAN
+    }
+}
+
+/// Conventional air density (kg/m³) used for weighing corrections (OIML R 33).
+pub const AIR_DENSITY_KG_M3: f64 = 1.2;
+
+/// Apparent specific gravity as obtained by weighing in air, sample at
+/// `t_sample` against pure water at `t_ref` (both °C, 0 dbar).
+///
+/// Each weighing reads the true mass minus the buoyancy of the displaced air,
+/// so the ratio becomes (ρ_sw − ρ_air) / (ρ_pw − ρ_air) with
+/// `AIR_DENSITY_KG_M3`. For seawater this is ~3e-5 above the vacuum SG.
+pub fn specific_gravity_in_air(sp: f64, t_sample: f64, t_ref: f64) -> f64 {
+    specific_gravity_air_corrected(sp, t_sample, t_ref, AIR_DENSITY_KG_M3)
+}
+
+/// `specific_gravity_in_air` with an explicit air density (kg/m³);
+/// `rho_air = 0.0` disables the correction and, for `t_sample == t_ref`,
+/// equals `specific_gravity(sp, t_ref, 0.0)`.
+pub fn specific_gravity_air_corrected(sp: f64, t_sample: f64, t_ref: f64, rho_air: f64) -> f64 {
+    let sa = sa_from_sp(sp);
+    let rho_sw = rho(sa, ct_from_t(sa, t_sample, 0.0), 0.0);
+    let rho_pw = rho(0.0, ct_from_t(0.0, t_ref, 0.0), 0.0);
+    (rho_sw - rho_air) / (rho_pw - rho_air)
+}
+
+/// SG 20/20 and SG 25/25 reported in summaries: at 0 dbar, or at
+/// `ass.pressure_dbar` when `ass.sg_at_sample_pressure` is set.
+pub(crate) fn reference_sgs(sp: f64, ass: &Assumptions) -> (f64, f64) {
+    let p_ref = if ass.sg_at_sample_pressure {
+        ass.pressure_dbar
+    } else {
+        0.0
+    };
+    (
+        specific_gravity(sp, 20.0, p_ref),
+        specific_gravity(sp, 25.0, p_ref),
+    )
 }
 
 /// Compute a compact `CalculationSummary` for the given inputs.
@@ -300,28 +1022,198 @@ pub fn specific_gravity(sp: f64, t_ref: f64, p_ref: f64) -> f64 {
 /// This convenience function runs the TEOS-10 based SP solver and returns a
 /// small summary useful for UI or API responses. The returned `CalculationSummary`
 /// contains both salinity (SP, SA), the in-situ density (kg/m³) and two
-/// reference specific gravities at 20°C and 25°C (both at 0 dbar).
+/// reference specific gravities at 20°C and 25°C (at 0 dbar unless
+/// `Assumptions::sg_at_sample_pressure` is set), plus the
+/// SP and mg/L ion table normalized to `Assumptions::salinity_norm`
+/// (component output is computed regardless of `return_components`).
 ///
 /// Notes:
 /// - The function uses `calc_salinity_sp_teos10` with conservative defaults
-///   for iteration (30 iterations and 1e-8 tolerance).
+///   for iteration (`SUMMARY_MAX_ITER` iterations and `SUMMARY_TOL` tolerance).
 /// - All units follow the crate convention: density in kg/m³, SA in g/kg,
 ///   and specific gravities are unitless ratios.
 pub fn compute_summary(inputs: &Inputs, assumptions: &Assumptions) -> CalculationSummary {
-    let sp = match calc_salinity_sp_teos10(inputs, assumptions, 30, 1e-8) {
-        CalcResult::Simple(v) => v,
-        CalcResult::Detailed(d) => d.sp,
-    };
-    let sa = sa_from_sp(sp);
+    summarize(inputs, assumptions).0
+}
+
+/// `compute_summary` for each `(inputs, assumptions)` pair, in order.
+///
+/// `progress`, when given, is called after every sample with
+/// `(completed, total)`; `None` adds nothing to the loop but a branch.
+pub fn compute_summaries(
+    samples: &[(Inputs, Assumptions)],
+    mut progress: Option<&mut dyn FnMut(usize, usize)>,
+) -> Vec<CalculationSummary> {
+    let total = samples.len();
+    samples
+        .iter()
+        .enumerate()
+        .map(|(i, (inputs, assumptions))| {
+            let summary = compute_summary(inputs, assumptions);
+            if let Some(report) = progress.as_mut() {
+                report(i + 1, total);
+            }
+            summary
+        })
+        .collect()
+}
+
+/// Shared body of `compute_summary` and `compute_full`; also returns the
+/// component tables and the one SP iteration both are built from.
+fn summarize(
+    inputs: &Inputs,
+    assumptions: &Assumptions,
+) -> (CalculationSummary, Components, Iteration) {
+    let assumptions = &assumptions.at_sample_conditions(inputs);
+    let normalized = assumptions.clone().normalized();
+    let budget = mass_budget(inputs, &normalized, None);
+    let iteration = iterate_sp(&budget, &normalized, SUMMARY_MAX_ITER, SUMMARY_TOL);
+    let d = detailed_result(inputs, &normalized, &budget, iteration.clone());
+    let sp = d.sp;
+    let normalized_sp = round_to(
+        sp * d.components.norm_factor,
+        assumptions.precision.sp as i32,
+    );
+    let normalized_ion_table = d.components.mg_l_sp35.clone();
+    let components = d.components;
+    // SA = SR + δSA. The bundled `gsw` release ships no SAAR atlas for a
+    // location-based anomaly; the anomaly sources are the nutrient correction
+    // and a user-supplied `delta_sa`, both zero unless given.
+    let sa_anomaly_g_kg = sa_anomaly(inputs, assumptions);
+    let sa = sa_from_sp(sp) + sa_anomaly_g_kg;
     let rho_val = rho_from_sp(sp, assumptions);
-    let sg_20 = specific_gravity(sp, 20.0, 0.0);
-    let sg_25 = specific_gravity(sp, 25.0, 0.0);
+    let (sg_20, sg_25) = reference_sgs(sp, assumptions);
 
-    CalculationSummary {
+    let summary = CalculationSummary {
         sp,
         sa,
+        sa_anomaly_g_kg,
         density_kg_per_m3: rho_val,
         sg_20_20: sg_20,
         sg_25_25: sg_25,
+        normalized_sp,
+        normalized_ion_table,
+        sa_composition_g_kg: assumptions
+            .report_composition_sa
+            .then(|| sa_from_composition(inputs, assumptions)),
+    };
+    (summary, components, iteration)
+}
+
+/// Everything a calculation produces, in one serializable envelope.
+///
+/// - `summary`: the `compute_summary` output
+/// - `converged`: whether the SP iteration met its tolerance
+/// - `iterations`: number of SP updates performed
+/// - `warnings`: non-fatal diagnostics (estimated species, range checks, …)
+/// - `components`: per-ion tables, present when `return_components` is set
+#[derive(Serialize, Debug)]
+pub struct FullResult {
+    pub summary: CalculationSummary,
+    pub converged: bool,
+    pub iterations: usize,
+    pub warnings: Vec<Warning>,
+    pub components: Option<Components>,
+    /// Effective configuration, present when `Assumptions::echo_config` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_used: Option<ConfigUsed>,
+}
+
+/// Reference composition and normalized assumptions a calculation ran with,
+/// for audit trails and reproducing a result.
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigUsed {
+    pub ref_alk_dkh: Option<f64>,
+    /// Borate fraction applied to B (0 when `assume_borate` is off).
+    pub borate_fraction: f64,
+    /// Reference Absolute Salinity (g/kg) of the SR scale.
+    pub sr_ref: f64,
+    pub assumptions: Assumptions,
+}
+
+/// Run the full calculation and collect summary, convergence and warnings.
+///
+/// This is the recommended library entry point. It uses the same iteration
+/// limits as `compute_summary` and always evaluates the component tables to
+/// flag estimated species, but only returns them when
+/// `assumptions.return_components` is set.
+pub fn compute_full(inputs: &Inputs, assumptions: &Assumptions) -> FullResult {
+    let ass = assumptions.at_sample_conditions(inputs).normalized();
+    let (summary, components, iteration) = summarize(inputs, assumptions);
+
+    let mut warnings = Vec::new();
+    if !iteration.converged {
+        warnings.push(Warning::NotConverged {
+            iterations: iteration.iterations,
+        });
+    }
+    warnings.extend(
+        components
+            .estimated
+            .iter()
+            .filter(|(_, est)| *est)
+            .map(|&(species, _)| Warning::Estimated { species }),
+    );
+    if ass.eos == EosKind::Teos10 && ass.temp < T_MIN_C {
+        warnings.push(Warning::OutOfDomain {
+            quantity: "temperature",
+        });
+    }
+    if !summary.density_kg_per_m3.is_finite() {
+        warnings.push(Warning::OutOfDomain {
+            quantity: "density",
+        });
+    }
+    if !(summary.sg_20_20.is_finite() && summary.sg_25_25.is_finite()) {
+        warnings.push(Warning::OutOfDomain {
+            quantity: "specific gravity",
+        });
+    }
+    let effective = effective_inputs(inputs, &ass);
+    if let Some(alk) = effective.measured_alk_dkh().or(ass.alkalinity)
+        && !(0.0..=ass.alk_ceiling_dkh).contains(&alk)
+    {
+        warnings.push(Warning::OutOfRange {
+            quantity: "alkalinity (dKH)",
+            value: alk,
+            min: 0.0,
+            max: ass.alk_ceiling_dkh,
+        });
+    }
+    if is_zero_panel(&effective) {
+        warnings.push(Warning::ZeroPanel);
+    } else if !looks_like_seawater_with(&effective, ass.treat_zero_as_missing) {
+        warnings.push(Warning::NotSeawater);
+    }
+    warnings.extend(reconcile_sulfate(&effective).1);
+    if ass.warn_contamination {
+        warnings.extend(diagnose_contamination(&effective).into_iter().map(|h| {
+            Warning::PossibleContamination {
+                salt: h.salt,
+                confidence: h.confidence,
+            }
+        }));
+    }
+    if !(SP_MIN_PSS78..=SP_MAX_PSS78).contains(&summary.sp) {
+        warnings.push(Warning::OutOfRange {
+            quantity: "SP",
+            value: summary.sp,
+            min: SP_MIN_PSS78,
+            max: SP_MAX_PSS78,
+        });
+    }
+
+    FullResult {
+        summary,
+        converged: iteration.converged,
+        iterations: iteration.iterations,
+        warnings,
+        components: assumptions.return_components.then_some(components),
+        config_used: ass.echo_config.then(|| ConfigUsed {
+            ref_alk_dkh: ass.ref_alk_dkh,
+            borate_fraction: effective_borate_fraction(&ass),
+            sr_ref: SR_REF,
+            assumptions: ass.clone(),
+        }),
     }
 }
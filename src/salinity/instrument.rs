@@ -0,0 +1,52 @@
+//! What common salinity instruments display for a sample of known SP.
+//!
+//! Refractometers, hydrometers and conductivity meters respond to different
+//! physical properties; `instrument_reading` maps true Practical Salinity to
+//! each instrument's native quantity so readings can be compared.
+
+use crate::adapters::teos10::conductivity_ms_cm;
+use crate::salinity::calculator::specific_gravity;
+
+/// Sodium D line (nm) used by handheld refractometers.
+pub const SODIUM_D_WAVELENGTH_NM: f64 = 589.3;
+
+/// Instrument whose display `instrument_reading` reproduces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instrument {
+    /// Refractive index nD at the sodium D line (dimensionless).
+    Refractometer,
+    /// Specific gravity t/t: sample and pure-water reference both at the
+    /// reading temperature, 0 dbar (glass expansion ignored).
+    Hydrometer,
+    /// Uncompensated conductivity (mS/cm) at the reading temperature, 0 dbar.
+    Conductivity,
+}
+
+/// Reading of `instrument` for a sample of Practical Salinity `sp` at
+/// temperature `t_c` (°C); see `Instrument` for the unit of each reading.
+pub fn instrument_reading(sp: f64, instrument: Instrument, t_c: f64) -> f64 {
+    match instrument {
+        Instrument::Refractometer => refractive_index(sp, t_c, SODIUM_D_WAVELENGTH_NM),
+        Instrument::Hydrometer => specific_gravity(sp, t_c, 0.0),
+        Instrument::Conductivity => conductivity_ms_cm(sp, t_c, 0.0),
+    }
+}
+
+/// Refractive index of seawater at atmospheric pressure after Quan & Fry
+/// (1995), Applied Optics 34(18): salinity `sp` (taken as ‰), temperature
+/// `t_c` (°C) and vacuum wavelength `wavelength_nm`.
+///
+/// Fitted for 0–35 ‰, 0–30 °C and 400–700 nm (~3e-5 accuracy); pure water
+/// at 20 °C gives nD ≈ 1.33300.
+pub fn refractive_index(sp: f64, t_c: f64, wavelength_nm: f64) -> f64 {
+    const N: [f64; 10] = [
+        1.314_05, 1.779e-4, -1.05e-6, 1.6e-8, -2.02e-6, 15.868, 0.011_55, -0.004_23, -4_382.0,
+        1.145_5e6,
+    ];
+    let (s, t, l) = (sp, t_c, wavelength_nm);
+    N[0] + (N[1] + N[2] * t + N[3] * t * t) * s
+        + N[4] * t * t
+        + (N[5] + N[6] * s + N[7] * t) / l
+        + N[8] / (l * l)
+        + N[9] / (l * l * l)
+}
@@ -0,0 +1,104 @@
+//! Precomputed SP-vs-(density, temperature) table for constrained targets.
+//!
+//! Building the table needs the full density routine; looking a value up
+//! afterwards is only interpolation, which suits embedded/no_std callers that
+//! cannot afford the `sp_from_density` root find at runtime.
+
+use crate::models::Assumptions;
+use crate::salinity::calculator::rho_from_sp;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Density table on a regular `steps` × `steps` grid of SP and temperature.
+#[derive(Clone, Debug)]
+pub struct Lut {
+    pub sp_range: (f64, f64),
+    pub t_range: (f64, f64),
+    pub steps: usize,
+    /// Densities (kg/m³), row-major by temperature: `rho[i_t * steps + i_sp]`.
+    pub rho: Vec<f64>,
+}
+
+/// Tabulate `rho_from_sp` over `sp_range` × `t_range` (°C) with `steps` nodes
+/// per axis (at least 2).
+///
+/// Pressure and equation of state come from `ass`; its temperature is
+/// replaced by the grid temperatures.
+pub fn build_salinity_lut(
+    sp_range: (f64, f64),
+    t_range: (f64, f64),
+    steps: usize,
+    ass: &Assumptions,
+) -> Lut {
+    let steps = steps.max(2);
+    let base = ass.clone().normalized();
+    let mut rho = Vec::with_capacity(steps * steps);
+    for i_t in 0..steps {
+        let ass = Assumptions {
+            temp: node(t_range, steps, i_t),
+            ..base.clone()
+        };
+        for i_sp in 0..steps {
+            rho.push(rho_from_sp(node(sp_range, steps, i_sp), &ass));
+        }
+    }
+    Lut {
+        sp_range,
+        t_range,
+        steps,
+        rho,
+    }
+}
+
+/// Value of grid node `i` on an axis spanning `range` with `steps` nodes.
+fn node(range: (f64, f64), steps: usize, i: usize) -> f64 {
+    range.0 + (range.1 - range.0) * i as f64 / (steps - 1) as f64
+}
+
+impl Lut {
+    /// Practical Salinity for density `rho_kg_m3` at temperature `t` (°C).
+    ///
+    /// Interpolates the density rows linearly in temperature, then inverts the
+    /// resulting SP profile linearly between the bracketing nodes. Returns
+    /// `NaN` when `t` or `rho_kg_m3` lies outside the table.
+    pub fn lookup_sp(&self, rho_kg_m3: f64, t: f64) -> f64 {
+        let n = self.steps;
+        let Some((i_t, w_t)) = cell(self.t_range, n, t) else {
+            return f64::NAN;
+        };
+        let rho_at = |i_sp: usize| {
+            let lo = self.rho[i_t * n + i_sp];
+            let hi = self.rho[(i_t + 1) * n + i_sp];
+            lo + w_t * (hi - lo)
+        };
+
+        let mut prev = rho_at(0);
+        for i_sp in 1..n {
+            let next = rho_at(i_sp);
+            if (prev..=next).contains(&rho_kg_m3) {
+                let w = if next > prev {
+                    (rho_kg_m3 - prev) / (next - prev)
+                } else {
+                    0.0
+                };
+                let sp_lo = node(self.sp_range, n, i_sp - 1);
+                let sp_hi = node(self.sp_range, n, i_sp);
+                return sp_lo + w * (sp_hi - sp_lo);
+            }
+            prev = next;
+        }
+        f64::NAN
+    }
+}
+
+/// Lower node index and fractional position of `x` within its grid cell, or
+/// `None` outside `range`.
+fn cell(range: (f64, f64), steps: usize, x: f64) -> Option<(usize, f64)> {
+    let pos = (x - range.0) / (range.1 - range.0) * (steps - 1) as f64;
+    if !(0.0..=(steps - 1) as f64).contains(&pos) {
+        return None;
+    }
+    let i = (pos.floor() as usize).min(steps - 2);
+    Some((i, pos - i as f64))
+}
@@ -1 +1,11 @@
+pub mod blend;
 pub mod calculator;
+pub mod instrument;
+pub mod lut;
+pub mod profile;
+pub mod properties;
+pub mod scales;
+pub mod sensitivity;
+pub mod standard;
+pub mod stats;
+pub mod sweep;
@@ -0,0 +1,44 @@
+//! Comparison of samples against a reference (climatological) profile or
+//! stored reference sample.
+
+use crate::models::{Assumptions, Inputs};
+use crate::salinity::calculator::compute_summary;
+
+/// Practical Salinity anomaly of a sample at `p_dbar`: `sample_sp` minus the
+/// reference SP linearly interpolated in pressure.
+///
+/// `reference_profile` holds `(pressure dbar, SP)` pairs ordered by pressure
+/// (either direction). Returns `NaN` when `p_dbar` lies outside the profile
+/// or the profile is empty; a sample exactly at a node uses that node.
+pub fn sp_anomaly(sample_sp: f64, reference_profile: &[(f64, f64)], p_dbar: f64) -> f64 {
+    sample_sp - reference_sp_at(reference_profile, p_dbar)
+}
+
+/// In-situ density anomaly (kg/m³) of a sample: its `compute_summary`
+/// density minus `reference_density`, e.g. that of a stored reference sample
+/// evaluated under the same assumptions.
+pub fn density_anomaly(inp: &Inputs, ass: &Assumptions, reference_density: f64) -> f64 {
+    compute_summary(inp, ass).density_kg_per_m3 - reference_density
+}
+
+/// Reference SP at `p_dbar` by linear interpolation, `NaN` outside.
+fn reference_sp_at(profile: &[(f64, f64)], p_dbar: f64) -> f64 {
+    if let [(p, sp)] = profile {
+        return if *p == p_dbar { *sp } else { f64::NAN };
+    }
+    profile
+        .windows(2)
+        .find_map(|w| {
+            let ((p0, sp0), (p1, sp1)) = (w[0], w[1]);
+            let (lo, hi) = if p0 <= p1 { (p0, p1) } else { (p1, p0) };
+            if !(lo..=hi).contains(&p_dbar) {
+                return None;
+            }
+            Some(if p1 == p0 {
+                sp0
+            } else {
+                sp0 + (sp1 - sp0) * (p_dbar - p0) / (p1 - p0)
+            })
+        })
+        .unwrap_or(f64::NAN)
+}
@@ -0,0 +1,54 @@
+//! TEOS-10 properties of a sample bundled in one call.
+
+use crate::adapters::teos10::{
+    alpha, beta, ct_freezing, ct_from_t, pt_from_t, rho, sa_from_sp, sigma0, sound_speed,
+    specific_heat,
+};
+use crate::models::Assumptions;
+use serde::Serialize;
+
+/// TEOS-10 properties at the temperature and pressure of `Assumptions`.
+///
+/// - `sa`: Absolute Salinity (g/kg), `ct`: Conservative Temperature (°C)
+/// - `density_kg_m3`: in-situ density, `sound_speed_m_s`: speed of sound
+/// - `alpha` (1/K), `beta` (kg/g): expansion/contraction coefficients
+/// - `cp`: isobaric specific heat (J/(kg·K))
+/// - `sigma0`: potential density anomaly at 0 dbar (kg/m³)
+/// - `ct_freezing`: freezing CT (°C) of air-saturated seawater
+/// - `pt_difference`: in-situ minus potential temperature (K), the adiabatic
+///   warming of the sample at `pressure_dbar` (`pt_from_t`)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Teos10Properties {
+    pub sa: f64,
+    pub ct: f64,
+    pub density_kg_m3: f64,
+    pub sound_speed_m_s: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub cp: f64,
+    pub sigma0: f64,
+    pub ct_freezing: f64,
+    pub pt_difference: f64,
+}
+
+/// Evaluate all `Teos10Properties` for Practical Salinity `sp`, converting
+/// SP → SA and t → CT once and reusing them.
+///
+/// Always uses the TEOS-10 equation of state; `ass.eos` is not consulted.
+pub fn teos10_properties(sp: f64, ass: &Assumptions) -> Teos10Properties {
+    let p = ass.pressure_dbar;
+    let sa = sa_from_sp(sp);
+    let ct = ct_from_t(sa, ass.temp, p);
+    Teos10Properties {
+        sa,
+        ct,
+        density_kg_m3: rho(sa, ct, p),
+        sound_speed_m_s: sound_speed(sa, ct, p),
+        alpha: alpha(sa, ct, p),
+        beta: beta(sa, ct, p),
+        cp: specific_heat(sa, ass.temp, p),
+        sigma0: sigma0(sa, ct),
+        ct_freezing: ct_freezing(sa, p, 1.0),
+        pt_difference: ass.temp - pt_from_t(sa, ass.temp, p),
+    }
+}
@@ -0,0 +1,28 @@
+//! Historical salinity scales based on chlorinity.
+//!
+//! Before PSS-78 salinity was derived from titrated chlorinity Cl (‰, g/kg).
+//! Knudsen's 1902 relation `S = 0.030 + 1.805 Cl` was replaced in 1969 by the
+//! proportional `S = 1.80655 Cl`, which PSS-78 was constructed to match at
+//! S = 35. The two agree at Cl = 19.374 ‰ but Knudsen's constant offset makes
+//! it read 0.030 high in fresh water, so the scales diverge at low chlorinity.
+
+/// Knudsen (1902) intercept in ‰.
+pub const KNUDSEN_OFFSET: f64 = 0.030;
+/// Knudsen (1902) slope.
+pub const KNUDSEN_SLOPE: f64 = 1.805;
+/// Salinity/chlorinity ratio of the 1969 definition carried into PSS-78.
+pub const SALINITY_PER_CHLORINITY: f64 = 1.806_55;
+
+/// Knudsen salinity (‰) from chlorinity (‰): `0.030 + 1.805 Cl`.
+pub fn knudsen_salinity(cl_permille: f64) -> f64 {
+    KNUDSEN_OFFSET + KNUDSEN_SLOPE * cl_permille
+}
+
+/// Practical Salinity from chlorinity (‰) via `S = 1.80655 Cl`.
+///
+/// This is the conversion used to bring chlorinity-era (Knudsen) records onto
+/// the PSS-78 scale; it is exact at Cl = 19.374 ‰ (SP = 35) and assumes
+/// standard seawater ionic ratios.
+pub fn sp_from_knudsen(cl_permille: f64) -> f64 {
+    SALINITY_PER_CHLORINITY * cl_permille
+}
@@ -0,0 +1,209 @@
+//! Local sensitivity of SP to the measured inputs.
+
+use crate::chemistry::Species;
+use crate::models::{Assumptions, ConcentrationBasis, ConcentrationUnit, Inputs};
+use crate::salinity::calculator::{effective_inputs, iterate_sp, mass_budget, rho_from_sp};
+use crate::salinity::standard::standard_seawater;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Relative step used for the central differences.
+const REL_STEP: f64 = 1e-3;
+
+type FieldAccess = fn(&mut Inputs) -> Option<&mut f64>;
+
+/// Perturbable inputs, keyed by their `Inputs` field name. Optional fields
+/// are only reported when supplied; sulfate uses whichever of `so4`/`s` the
+/// solver reads, and alkalinity whichever measured unit is set.
+const FIELDS: [(&str, FieldAccess); 12] = [
+    ("na", |i| Some(&mut i.na)),
+    ("ca", |i| Some(&mut i.ca)),
+    ("mg", |i| Some(&mut i.mg)),
+    ("k", |i| Some(&mut i.k)),
+    ("sr", |i| Some(&mut i.sr)),
+    ("br", |i| Some(&mut i.br)),
+    ("cl", |i| i.cl.as_mut()),
+    ("f", |i| i.f.as_mut()),
+    ("so4", |i| i.so4.as_mut()),
+    ("s", |i| {
+        if i.so4.is_some() {
+            None
+        } else {
+            Some(&mut i.s)
+        }
+    }),
+    ("b", |i| Some(&mut i.b)),
+    ("alk", |i| match i.alk_dkh {
+        Some(_) => i.alk_dkh.as_mut(),
+        None => i.alk_ppm_caco3.as_mut(),
+    }),
+];
+
+/// Sensitivity of SP to each supplied input, as `∂SP/∂ln(c)`.
+///
+/// Each value is the SP change per unit relative change of that input
+/// (so `0.01 * value` is the SP shift for a +1 % error), estimated by a
+/// central difference on the unrounded solver output. Entries are in `Inputs`
+/// field order; zero concentrations yield `0.0`. Estimated chloride is not a
+/// measured input and is reported only when `inp.cl` is set.
+pub fn sp_sensitivities(inp: &Inputs, ass: &Assumptions) -> Vec<(&'static str, f64)> {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    let sp_of = |i: &Inputs| iterate_sp(&mass_budget(i, &ass, None), &ass, 50, 1e-12).sp;
+
+    FIELDS
+        .iter()
+        .filter_map(|&(name, field)| {
+            let base = *field(&mut inp.clone())?;
+            let sp_at = |factor: f64| {
+                let mut perturbed = inp.clone();
+                if let Some(v) = field(&mut perturbed) {
+                    *v = base * factor;
+                }
+                sp_of(&perturbed)
+            };
+            let d = (sp_at(1.0 + REL_STEP) - sp_at(1.0 - REL_STEP)) / (2.0 * REL_STEP);
+            Some((name, d))
+        })
+        .collect()
+}
+
+/// Each `Species` with the `sp_sensitivities` key it is reported under for
+/// `standard_seawater` (which supplies sulfate as elemental `s`).
+const SPECIES_FIELDS: [(Species, &str); 10] = [
+    (Species::Na, "na"),
+    (Species::Ca, "ca"),
+    (Species::Mg, "mg"),
+    (Species::K, "k"),
+    (Species::Sr, "sr"),
+    (Species::Br, "br"),
+    (Species::Cl, "cl"),
+    (Species::F, "f"),
+    (Species::So4, "s"),
+    (Species::B, "b"),
+];
+
+/// Smallest set of ions to measure so that SP of standard seawater stays
+/// within `target_accuracy` (relative, e.g. `0.01` for 1 %) of the full-panel
+/// value.
+///
+/// Starting from the complete reference panel at SP 35, ions are dropped
+/// greedily in order of increasing `sp_sensitivities` magnitude and kept out
+/// whenever the resulting SP error stays within the target. Dropped chloride
+/// is estimated by the solver (`estimate_cl_mg_l`), dropped fluoride falls
+/// back to `Assumptions::default_f_mg_l`, and any other dropped ion reads as
+/// absent. The result is in `Inputs` field order; alkalinity is not an ion
+/// species and is always taken as measured.
+pub fn minimal_ion_set(target_accuracy: f64) -> Vec<Species> {
+    let ass = Assumptions::default().normalized();
+    let reference = standard_seawater(35.0, &ass);
+    let sp_of = |i: &Inputs| iterate_sp(&mass_budget(i, &ass, None), &ass, 50, 1e-12).sp;
+    let sp_ref = sp_of(&reference);
+    let tolerance = target_accuracy.max(0.0) * sp_ref;
+
+    let sens = sp_sensitivities(&reference, &ass);
+    let mut order: Vec<(Species, f64)> = SPECIES_FIELDS
+        .iter()
+        .map(|&(species, key)| {
+            let d = sens
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map_or(0.0, |(_, d)| *d);
+            (species, d.abs())
+        })
+        .collect();
+    order.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let mut measured: Vec<Species> = SPECIES_FIELDS.iter().map(|&(s, _)| s).collect();
+    for (species, _) in order {
+        let trial: Vec<Species> = measured.iter().copied().filter(|s| *s != species).collect();
+        if (sp_of(&panel_without(&reference, &trial)) - sp_ref).abs() <= tolerance {
+            measured = trial;
+        }
+    }
+    measured
+}
+
+/// Unrounded SP of `inp` with `species` dropped from the panel.
+///
+/// The species is removed as in `minimal_ion_set`: chloride becomes missing
+/// and is re-estimated by the solver, fluoride falls back to
+/// `Assumptions::default_f_mg_l`, and any other ion is set to zero (a
+/// missing chloride is then re-estimated from the remaining ions). Compare
+/// with the SP of the full panel to see what one measurement contributes.
+pub fn sp_without_ion(inp: &Inputs, ass: &Assumptions, species: Species) -> f64 {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    let measured: Vec<Species> = SPECIES_FIELDS
+        .iter()
+        .map(|&(s, _)| s)
+        .filter(|&s| s != species)
+        .collect();
+    let dropped = panel_without(inp, &measured);
+    iterate_sp(&mass_budget(&dropped, &ass, None), &ass, 50, 1e-12).sp
+}
+
+/// Expected SP bias (unrounded) from the estimated parts of an incomplete
+/// panel.
+///
+/// Compares the SP of `inp` as given — missing chloride estimated, missing
+/// fluoride at `Assumptions::default_f_mg_l`, missing alkalinity from
+/// `Assumptions::alkalinity` or the reference — with the SP of the same panel
+/// after each missing value is replaced by its standard-ratio concentration
+/// (`standard_seawater` at the estimated SP). Returns the estimated minus the
+/// filled SP, so a positive bias means the estimation reads high; a complete
+/// panel returns zero.
+pub fn estimated_sp_bias(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let ass = ass.at_sample_conditions(inp).normalized();
+    let sp_of = |i: &Inputs, a: &Assumptions| iterate_sp(&mass_budget(i, a, None), a, 50, 1e-12).sp;
+    let sp_estimated = sp_of(inp, &ass);
+
+    // Fill in on the undiluted mg/L panel; per-kilogram panels take the
+    // standard values per kilogram too (alkalinity is always per litre).
+    let standard = standard_seawater(sp_estimated, &ass);
+    let kg_per_l = match ass.input_basis {
+        ConcentrationBasis::PerLiter => 1.0,
+        ConcentrationBasis::PerKilogram => rho_from_sp(sp_estimated, &ass) / 1000.0,
+    };
+    let mut filled = effective_inputs(inp, &ass);
+    if filled.cl.filter(|&c| c > 0.0).is_none() {
+        filled.cl = standard.cl.map(|c| c / kg_per_l);
+    }
+    if filled.f.is_none() {
+        filled.f = standard.f.map(|f| f / kg_per_l);
+    }
+    if filled.measured_alk_dkh().is_none() {
+        filled.alk_dkh = standard.alk_dkh;
+    }
+    let ass_mg_l = Assumptions {
+        input_units: ConcentrationUnit::MgPerL,
+        ..ass.clone()
+    };
+    sp_estimated - sp_of(&filled, &ass_mg_l)
+}
+
+/// `panel` keeping only the `measured` species; Cl and F become `None`
+/// (estimated / defaulted), the rest zero.
+fn panel_without(panel: &Inputs, measured: &[Species]) -> Inputs {
+    let mut out = panel.clone();
+    for &(species, _) in &SPECIES_FIELDS {
+        if measured.contains(&species) {
+            continue;
+        }
+        match species {
+            Species::Na => out.na = 0.0,
+            Species::Ca => out.ca = 0.0,
+            Species::Mg => out.mg = 0.0,
+            Species::K => out.k = 0.0,
+            Species::Sr => out.sr = 0.0,
+            Species::Br => out.br = 0.0,
+            Species::Cl => out.cl = None,
+            Species::F => out.f = None,
+            Species::So4 => {
+                out.s = 0.0;
+                out.so4 = None;
+            }
+            Species::B => out.b = 0.0,
+        }
+    }
+    out
+}
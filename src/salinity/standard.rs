@@ -0,0 +1,131 @@
+use crate::adapters::teos10::sa_from_sp;
+use crate::chemistry::*;
+use crate::models::{Assumptions, Inputs};
+use crate::salinity::calculator::{
+    SUMMARY_MAX_ITER, SUMMARY_TOL, compute_summary, iterate_sp, mass_budget, rho_from_sp,
+};
+
+/// Build an `Inputs` panel of standard-ratio seawater at the given SP.
+///
+/// The reference composition (`REF_MMOL_*`, mmol/kg at SP = 35) is scaled
+/// linearly by `sp / 35` and converted to mg/L with the TEOS-10 density at the
+/// temperature and pressure in `ass`. Sulfate and boron are reported on the
+/// elemental basis (`s`, `b`) expected by the solver; alkalinity follows the
+/// (normalized) reference alkalinity scaled by the same factor.
+///
+/// Feeding the result back into `calc_salinity_sp_teos10` with the same
+/// assumptions reproduces `sp` to within the solver tolerance.
+pub fn standard_seawater(sp: f64, ass: &Assumptions) -> Inputs {
+    let ass = ass.clone().normalized();
+    let sp = sp.max(0.0);
+    let scale = sa_from_sp(sp) / SR_REF;
+    let kg_per_l = rho_from_sp(sp, &ass) / 1000.0;
+
+    // mmol/kg -> mg/L: mmol/kg * g/mol = mg/kg, times kg/L.
+    let mg_l = |mmol_kg: f64, molar_mass: f64| mmol_kg * scale * molar_mass * kg_per_l;
+
+    Inputs {
+        na: mg_l(REF_MMOL_NA, M_NA),
+        ca: mg_l(REF_MMOL_CA, M_CA),
+        mg: mg_l(REF_MMOL_MG, M_MG),
+        k: mg_l(REF_MMOL_K, M_K),
+        sr: mg_l(REF_MMOL_SR, M_SR),
+        br: mg_l(REF_MMOL_BR, M_BR),
+        cl: Some(mg_l(REF_MMOL_CL, M_CL)),
+        f: Some(mg_l(REF_MMOL_F, M_F)),
+        s: mg_l(REF_MMOL_SO4, M_S),
+        b: mg_l(REF_MMOL_B, M_B),
+        alk_dkh: Some(ass.ref_alk_dkh.unwrap_or(0.0) * scale),
+        ..Default::default()
+    }
+}
+
+/// Predicted in-situ density (kg/m³) of `salt_g` grams of dry salt mix
+/// dissolved in `water_l` litres of pure water, at the conditions in `ass`.
+///
+/// The salt is assumed to have standard seawater ionic ratios, so its mass
+/// fraction of the solution is the Absolute Salinity; the `standard_seawater`
+/// panel at the implied SP is then evaluated with `compute_summary`. The
+/// water is weighed at its pure-water density under `ass`. Returns `NaN`
+/// when there is neither salt nor water.
+pub fn recipe_density(salt_g: f64, water_l: f64, ass: &Assumptions) -> f64 {
+    let ass = ass.clone().normalized();
+    let salt_kg = salt_g.max(0.0) / 1000.0;
+    let water_kg = water_l.max(0.0) * rho_from_sp(0.0, &ass) / 1000.0;
+    let total_kg = salt_kg + water_kg;
+    if total_kg <= 0.0 {
+        return f64::NAN;
+    }
+    let sa = 1000.0 * salt_kg / total_kg;
+    let sp = 35.0 * sa / SR_REF;
+    compute_summary(&standard_seawater(sp, &ass), &ass).density_kg_per_m3
+}
+
+/// Largest |SP(panel) − SP| for which `reconstruct_panel_checked` calls a
+/// reconstruction consistent.
+pub const RECONSTRUCT_SP_TOLERANCE: f64 = 0.5;
+
+/// Outcome of `reconstruct_panel_checked`.
+#[derive(Clone, Debug)]
+pub struct PanelReconstruction {
+    /// Standard-ratio panel scaled so the anchor ion matches its measurement.
+    pub inputs: Inputs,
+    /// SP the solver derives from `inputs`.
+    pub panel_sp: f64,
+    /// Whether `panel_sp` lies within `RECONSTRUCT_SP_TOLERANCE` of the
+    /// measured SP, i.e. the anchor ion is in standard ratio to the salinity.
+    pub consistent: bool,
+}
+
+/// Implied full standard-ratio panel (mg/L) from Practical Salinity `sp` and
+/// one measured ion `anchor` = `(species, mg/L)`, at the default assumptions.
+/// See `reconstruct_panel_checked` for the consistency check.
+pub fn reconstruct_panel(sp: f64, anchor: (Species, f64)) -> Inputs {
+    reconstruct_panel_checked(sp, anchor, &Assumptions::default()).inputs
+}
+
+/// `reconstruct_panel` at the conditions in `ass`, with its SP check.
+///
+/// Starts from `standard_seawater(sp, ass)` and scales every concentration
+/// by the ratio of the measured anchor to its standard value, so the anchor
+/// is reproduced exactly and the other ions follow the reference ratios.
+/// The panel is then run through the solver; a large gap between its SP and
+/// `sp` means the anchor ion is not in standard ratio to the salinity.
+/// When the anchor is not positive (or absent from standard seawater at
+/// `sp`) nothing can be scaled: the unscaled standard panel is returned with
+/// `panel_sp = NaN` and `consistent = false`.
+pub fn reconstruct_panel_checked(
+    sp: f64,
+    anchor: (Species, f64),
+    ass: &Assumptions,
+) -> PanelReconstruction {
+    let ass = ass.clone().normalized();
+    let (species, measured) = anchor;
+    let standard = standard_seawater(sp, &ass);
+    let expected = species.mg_l(&standard);
+    if !(measured > 0.0 && expected > 0.0) {
+        return PanelReconstruction {
+            inputs: standard,
+            panel_sp: f64::NAN,
+            consistent: false,
+        };
+    }
+    let inputs = Inputs {
+        dilution_factor: Some(measured / expected),
+        ..standard
+    }
+    .undiluted();
+
+    let panel_sp = iterate_sp(
+        &mass_budget(&inputs, &ass, None),
+        &ass,
+        SUMMARY_MAX_ITER,
+        SUMMARY_TOL,
+    )
+    .sp;
+    PanelReconstruction {
+        inputs,
+        panel_sp,
+        consistent: (panel_sp - sp).abs() <= RECONSTRUCT_SP_TOLERANCE,
+    }
+}
@@ -0,0 +1,57 @@
+//! Aggregate statistics over a batch of results.
+
+use crate::salinity::calculator::CalculationSummary;
+use serde::Serialize;
+
+/// Mean, extremes and population standard deviation of one quantity.
+///
+/// All fields are `NaN` for an empty batch.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Stat {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub stddev: f64,
+}
+
+impl Stat {
+    fn of(values: impl Iterator<Item = f64> + Clone) -> Self {
+        let n = values.clone().count();
+        if n == 0 {
+            return Stat {
+                mean: f64::NAN,
+                min: f64::NAN,
+                max: f64::NAN,
+                stddev: f64::NAN,
+            };
+        }
+        let mean = values.clone().sum::<f64>() / n as f64;
+        let variance = values.clone().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n as f64;
+        Stat {
+            mean,
+            min: values.clone().fold(f64::INFINITY, f64::min),
+            max: values.fold(f64::NEG_INFINITY, f64::max),
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Batch-wide statistics of SP, SA (g/kg) and in-situ density (kg/m³).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct BatchStats {
+    pub count: usize,
+    pub sp: Stat,
+    pub sa: Stat,
+    pub density_kg_per_m3: Stat,
+}
+
+/// Summarize `summaries` (e.g. from `compute_summaries`); the standard
+/// deviation is the population one (divided by the count).
+pub fn batch_statistics(summaries: &[CalculationSummary]) -> BatchStats {
+    BatchStats {
+        count: summaries.len(),
+        sp: Stat::of(summaries.iter().map(|s| s.sp)),
+        sa: Stat::of(summaries.iter().map(|s| s.sa)),
+        density_kg_per_m3: Stat::of(summaries.iter().map(|s| s.density_kg_per_m3)),
+    }
+}
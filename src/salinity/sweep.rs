@@ -0,0 +1,70 @@
+//! Evaluate one sample across a range of conditions.
+
+use crate::models::{Assumptions, Inputs};
+use crate::salinity::calculator::{
+    CalculationSummary, compute_summary, reference_sgs, rho_from_sp,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Compute a `CalculationSummary` for each temperature in `temps` (°C).
+///
+/// All other assumptions are taken from `base_ass`, which is normalized once up
+/// front. SP is composition-driven and changes only through the mg/L → g/kg
+/// density conversion, whereas density and SG follow the temperature.
+/// The swept temperature replaces any per-sample `inp.t_c`.
+pub fn summary_sweep_temperature(
+    inp: &Inputs,
+    base_ass: &Assumptions,
+    temps: &[f64],
+) -> Vec<CalculationSummary> {
+    let base = base_ass.clone().normalized();
+    let inp = &Inputs {
+        t_c: None,
+        ..inp.clone()
+    };
+    temps
+        .iter()
+        .map(|&temp| {
+            let ass = Assumptions {
+                temp,
+                ..base.clone()
+            };
+            compute_summary(inp, &ass)
+        })
+        .collect()
+}
+
+/// Compute a `CalculationSummary` for each pressure in `pressures` (dbar).
+///
+/// The panel is taken as measured at `base_ass` conditions (or the sample's
+/// own `t_c`/`p_dbar`, see `Assumptions::at_sample_conditions`), so SP, SA and the
+/// normalized tables are solved once there and shared; only the in-situ
+/// density (and SG, with `sg_at_sample_pressure`) is re-evaluated at each
+/// pressure. Useful for building a depth
+/// profile from a single surface panel.
+pub fn summary_sweep_pressure(
+    inp: &Inputs,
+    base_ass: &Assumptions,
+    pressures: &[f64],
+) -> Vec<CalculationSummary> {
+    let base = base_ass.at_sample_conditions(inp).normalized();
+    let summary = compute_summary(inp, &base);
+    pressures
+        .iter()
+        .map(|&pressure_dbar| {
+            let ass = Assumptions {
+                pressure_dbar,
+                ..base.clone()
+            };
+            let (sg_20_20, sg_25_25) = reference_sgs(summary.sp, &ass);
+            CalculationSummary {
+                density_kg_per_m3: rho_from_sp(summary.sp, &ass),
+                sg_20_20,
+                sg_25_25,
+                ..summary.clone()
+            }
+        })
+        .collect()
+}
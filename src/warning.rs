@@ -0,0 +1,74 @@
+//! Non-fatal diagnostics attached to calculation results.
+
+use core::fmt;
+use serde::Serialize;
+
+/// A condition worth surfacing to the caller that does not stop the
+/// calculation. Serialized with a `kind` tag, e.g.
+/// `{"kind":"estimated","species":"Cl-"}`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Warning {
+    /// The SP iteration hit its iteration limit before meeting the tolerance.
+    NotConverged { iterations: usize },
+    /// A species was estimated or taken from an assumption default.
+    Estimated { species: &'static str },
+    /// A result lies outside the validated range of the method.
+    OutOfRange {
+        quantity: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    /// A quantity could not be evaluated because its inputs lie outside the
+    /// TEOS-10 domain (reported as NaN in the summary).
+    OutOfDomain { quantity: &'static str },
+    /// Major-ion ratios do not resemble seawater (see `looks_like_seawater`).
+    NotSeawater,
+    /// Ion excesses suggest the sample was spiked with a salt (see
+    /// `diagnose_contamination`); only with `Assumptions::warn_contamination`.
+    PossibleContamination { salt: &'static str, confidence: f64 },
+    /// Both `s` and `so4` were reported and disagree (see
+    /// `reconcile_sulfate`); `so4` was used.
+    SulfateConflict { so4_mg_l: f64, so4_from_s_mg_l: f64 },
+    /// The panel reports no ions or alkalinity (see `is_zero_panel`); SP is 0
+    /// and the normalized tables are zeroed.
+    ZeroPanel,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::NotConverged { iterations } => {
+                write!(f, "solver did not converge after {iterations} iterations")
+            }
+            Warning::Estimated { species } => write!(f, "{species} was estimated, not measured"),
+            Warning::OutOfRange {
+                quantity,
+                value,
+                min,
+                max,
+            } => write!(f, "{quantity} = {value} is outside [{min}, {max}]"),
+            Warning::OutOfDomain { quantity } => {
+                write!(f, "{quantity} is outside the TEOS-10 domain")
+            }
+            Warning::NotSeawater => {
+                write!(f, "major-ion ratios do not resemble seawater")
+            }
+            Warning::PossibleContamination { salt, confidence } => {
+                write!(
+                    f,
+                    "ion excesses suggest {salt} (confidence {confidence:.2})"
+                )
+            }
+            Warning::SulfateConflict {
+                so4_mg_l,
+                so4_from_s_mg_l,
+            } => write!(
+                f,
+                "so4 = {so4_mg_l} mg/L disagrees with s ({so4_from_s_mg_l:.1} mg/L as SO4); using so4"
+            ),
+            Warning::ZeroPanel => write!(f, "no ions or alkalinity reported; SP is 0"),
+        }
+    }
+}
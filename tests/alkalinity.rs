@@ -0,0 +1,291 @@
+use salinity_rs::{Inputs, chemistry::*};
+
+fn approx_eq(v: f64, expected: f64, tol: f64) {
+    assert!(
+        (v - expected).abs() <= tol,
+        "value {v} differs from expected {expected} by more than {tol}"
+    );
+}
+
+#[test]
+fn ppm_caco3_matches_equivalent_dkh_species() {
+    let inputs = Inputs {
+        alk_ppm_caco3: Some(143.0),
+        ..Default::default()
+    };
+    let dkh = inputs.measured_alk_dkh().expect("ppm CaCO3 should resolve");
+    approx_eq(dkh, 8.0, 0.01);
+
+    let (h_ppm, c_ppm, o_ppm, _) = alk_species_from_dkh(dkh, None);
+    let (h_dkh, c_dkh, o_dkh, _) = alk_species_from_dkh(8.0, None);
+    for (a, b) in [(h_ppm, h_dkh), (c_ppm, c_dkh), (o_ppm, o_dkh)] {
+        assert!(
+            (a / b - 1.0).abs() < 1e-3,
+            "species moles differ: {a} vs {b}"
+        );
+    }
+}
+
+#[test]
+fn alk_dkh_takes_precedence_over_ppm_caco3() {
+    let inputs = Inputs {
+        alk_dkh: Some(7.0),
+        alk_ppm_caco3: Some(143.0),
+        ..Default::default()
+    };
+    assert_eq!(inputs.measured_alk_dkh(), Some(7.0));
+    assert_eq!(Inputs::default().measured_alk_dkh(), None);
+}
+
+#[test]
+fn alkalinity_and_boron_contribute_little_to_sp() {
+    use salinity_rs::{Assumptions, nonconservative_salinity_contribution, standard_seawater};
+
+    let ass = Assumptions::default();
+    let reef = Inputs {
+        alk_dkh: Some(12.0),
+        ..standard_seawater(35.0, &ass)
+    };
+    let contribution = nonconservative_salinity_contribution(&reef, &ass);
+    assert!(
+        contribution > 0.0 && contribution < 0.5,
+        "unexpected contribution {contribution}"
+    );
+
+    let bare = Inputs {
+        alk_dkh: Some(0.0),
+        b: 0.0,
+        ..reef
+    };
+    assert_eq!(nonconservative_salinity_contribution(&bare, &ass), 0.0);
+}
+
+#[test]
+fn extreme_alkalinity_is_flagged_but_used() {
+    use salinity_rs::{Assumptions, Inputs, Warning, compute_full, standard_seawater};
+
+    let ass = Assumptions::default();
+    let alk_warning = |w: &Warning| matches!(w, Warning::OutOfRange { quantity, .. } if quantity.starts_with("alkalinity"));
+    let with_alk = |alk_dkh: f64| Inputs {
+        alk_dkh: Some(alk_dkh),
+        ..standard_seawater(35.0, &ass)
+    };
+
+    let normal = compute_full(&with_alk(8.0), &ass);
+    assert!(!normal.warnings.iter().any(alk_warning));
+
+    let typo = compute_full(&with_alk(500.0), &ass);
+    assert!(typo.warnings.iter().any(alk_warning));
+    assert!(typo.summary.sp > normal.summary.sp);
+
+    let lenient = Assumptions {
+        alk_ceiling_dkh: 1_000.0,
+        ..ass.clone()
+    };
+    assert!(
+        !compute_full(&with_alk(500.0), &lenient)
+            .warnings
+            .iter()
+            .any(alk_warning)
+    );
+}
+
+#[test]
+fn expanded_alkalinity_species_sum_to_alkalinity_equivalents() {
+    use salinity_rs::{Assumptions, CalcResult, calc_salinity_sp_teos10, standard_seawater};
+
+    let lumped = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+    let expanded = Assumptions {
+        expand_alk_species: true,
+        ..lumped.clone()
+    };
+    let inputs = Inputs {
+        alk_dkh: Some(8.0),
+        ..standard_seawater(35.0, &lumped)
+    };
+    let run = |ass: &Assumptions| match calc_salinity_sp_teos10(&inputs, ass, 30, 1e-8) {
+        CalcResult::Detailed(d) => d,
+        CalcResult::Simple(_) => panic!("expected detailed output"),
+    };
+    let (a, b) = (run(&lumped), run(&expanded));
+    assert_eq!(a.sp, b.sp);
+
+    let row = |name: &str| {
+        b.components
+            .mg_l
+            .iter()
+            .find(|(k, _)| *k == name)
+            .map(|&(_, v)| v)
+    };
+    assert!(row("Alk.").is_none());
+    assert!(a.components.mg_l.iter().any(|(k, _)| *k == "Alk."));
+    let meq_l = row("HCO3-").unwrap() / M_HCO3
+        + 2.0 * row("CO3^2-").unwrap() / M_CO3
+        + row("OH-").unwrap() / M_OH;
+    approx_eq(meq_l, 8.0 * DKH_TO_MEQL, 1e-9);
+    assert!(row("CO3^2-").unwrap() > 0.0);
+}
+
+#[test]
+fn silicate_adds_a_little_total_alkalinity_at_seawater_ph() {
+    use salinity_rs::chemistry::nutrients::silicate_alkalinity;
+
+    let base = Inputs {
+        alk_dkh: Some(8.0),
+        ph: Some(8.1),
+        ..Default::default()
+    };
+    let with_si = Inputs {
+        si_umol_kg: Some(100.0),
+        ..base.clone()
+    };
+    let delta = total_alkalinity_meq_l(&with_si, None) - total_alkalinity_meq_l(&base, None);
+    // pKSi 9.38: about 5 % of silicate is SiO(OH)3- at pH 8.1, i.e. ~5 µeq.
+    approx_eq(delta, silicate_alkalinity(100.0, 8.1) / 1000.0, 1e-15);
+    assert!((0.004..0.006).contains(&delta), "{delta} meq/L");
+
+    // Without pH the nutrient terms are not evaluated.
+    let no_ph = Inputs {
+        ph: None,
+        ..with_si
+    };
+    assert_eq!(total_alkalinity_meq_l(&no_ph, None), 8.0 * DKH_TO_MEQL);
+}
+
+#[test]
+fn alkalinity_models_split_the_same_alkalinity() {
+    use salinity_rs::chemistry::alkalinity::{AlkalinityModel, EquilibriumBased, FixedFraction};
+    use salinity_rs::{Assumptions, CalcResult, calc_salinity_sp_iterative_with_models};
+
+    let (h, c, o, _) = alk_species_from_dkh(8.0, None);
+    assert_eq!(
+        FixedFraction.speciate(8.0, 25.0, 35.0, Some(8.1)),
+        (h, c, o)
+    );
+    assert_eq!(EquilibriumBased.speciate(8.0, 25.0, 35.0, None), (h, c, o));
+
+    let (h_eq, c_eq, o_eq) = EquilibriumBased.speciate(8.0, 25.0, 35.0, Some(8.1));
+    assert!((c_eq - c).abs() > 1e-5, "CO3 {c_eq} vs fixed {c}");
+    // Same alkalinity, different split.
+    approx_eq(h_eq + 2.0 * c_eq + o_eq, 8.0 * DKH_TO_MEQL / 1000.0, 1e-15);
+    assert!(
+        EquilibriumBased.speciate(8.0, 25.0, 35.0, Some(8.4)).1 > c_eq,
+        "CO3 should rise with pH"
+    );
+
+    let inputs = Inputs {
+        ph: Some(8.1),
+        ..salinity_rs::standard_seawater(35.0, &Assumptions::default())
+    };
+    let ass = Assumptions {
+        return_components: true,
+        expand_alk_species: true,
+        ..Default::default()
+    };
+    let run = |model: Option<&dyn AlkalinityModel>| match calc_salinity_sp_iterative_with_models(
+        &inputs, &ass, 50, 1e-10, None, model,
+    ) {
+        CalcResult::Detailed(d) => d,
+        CalcResult::Simple(_) => panic!("expected detailed output"),
+    };
+    let co3 = |d: &salinity_rs::DetailedResult| {
+        d.components
+            .mg_l
+            .iter()
+            .find(|(k, _)| *k == "CO3^2-")
+            .map(|(_, v)| *v)
+            .unwrap()
+    };
+    let (fixed, equilibrium) = (run(None), run(Some(&EquilibriumBased)));
+    assert_eq!(fixed.sp, equilibrium.sp);
+    assert!((co3(&fixed) - co3(&equilibrium)).abs() > 0.1);
+}
+
+#[test]
+fn equilibrium_split_follows_the_salinity() {
+    use salinity_rs::chemistry::alkalinity::{AlkalinityModel, EquilibriumBased, carbonate_k2};
+    use salinity_rs::{Assumptions, CalcResult, calc_salinity_sp_iterative_with_models};
+
+    // K2 rises with salinity, so at a fixed pH brackish water holds less
+    // CO3^2- than seawater.
+    let (_, co3_10, _) = EquilibriumBased.speciate(8.0, 20.0, 10.0, Some(8.1));
+    let (_, co3_35, _) = EquilibriumBased.speciate(8.0, 20.0, 35.0, Some(8.1));
+    assert!(
+        co3_35 > 1.5 * co3_10,
+        "CO3 {co3_10} at SP 10 vs {co3_35} at SP 35"
+    );
+
+    // The solver speciates at the solved SP, not at a fixed SP 35.
+    let ass = Assumptions {
+        return_components: true,
+        expand_alk_species: true,
+        ..Default::default()
+    };
+    let co3_per_hco3 = |sp: f64| {
+        let inputs = Inputs {
+            ph: Some(8.1),
+            ..salinity_rs::standard_seawater(sp, &ass)
+        };
+        let d = match calc_salinity_sp_iterative_with_models(
+            &inputs,
+            &ass,
+            50,
+            1e-10,
+            None,
+            Some(&EquilibriumBased),
+        ) {
+            CalcResult::Detailed(d) => d,
+            CalcResult::Simple(_) => panic!("expected detailed output"),
+        };
+        let row = |name: &str| {
+            d.components
+                .mg_l
+                .iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| *v)
+                .unwrap()
+        };
+        ((row("CO3^2-") / M_CO3) / (row("HCO3-") / M_HCO3), d.sp)
+    };
+    for target in [10.0, 35.0] {
+        let (ratio, sp) = co3_per_hco3(target);
+        let expected = carbonate_k2(ass.temp, sp) / 10f64.powf(-8.1);
+        approx_eq(ratio / expected, 1.0, 1e-3);
+    }
+    assert!(co3_per_hco3(35.0).0 > 1.5 * co3_per_hco3(10.0).0);
+}
+
+#[test]
+fn alk_mg_per_meq_shifts_measured_and_reference_mass_together() {
+    use salinity_rs::{Assumptions, compute_summary, effective_ref_sum};
+
+    let caco3 = Assumptions::default();
+    let hco3 = Assumptions {
+        alk_mg_per_meq: Some(61.017),
+        ..Default::default()
+    };
+    let shift = effective_ref_sum(&hco3) - effective_ref_sum(&caco3);
+    approx_eq(
+        shift,
+        8.0 * DKH_TO_MEQL * (61.017 - MG_PER_MEQ_AS_CACO3) / 1000.0,
+        1e-12,
+    );
+
+    for sp in [30.0, 35.0] {
+        let inputs = salinity_rs::standard_seawater(sp, &caco3);
+        let sp_caco3 = compute_summary(&inputs, &caco3).sp;
+        let sp_hco3 = compute_summary(&inputs, &hco3).sp;
+        approx_eq(sp_hco3, sp_caco3, 1e-3);
+    }
+
+    // Invalid overrides fall back to the CaCO3 convention on both sides.
+    let invalid = Assumptions {
+        alk_mg_per_meq: Some(f64::NAN),
+        ..Default::default()
+    };
+    assert_eq!(effective_ref_sum(&invalid), effective_ref_sum(&caco3));
+    assert_eq!(effective_alk_mg_per_meq(Some(-1.0)), MG_PER_MEQ_AS_CACO3);
+}
@@ -1,3 +1,4 @@
+use salinity_rs::chemistry::chloride::EstimationContext;
 use salinity_rs::{Assumptions, Inputs, chemistry::*};
 
 #[test]
@@ -14,6 +15,7 @@ fn estimates_chloride_close_to_reference_when_missing() {
         s: 814.0,
         b: 5.57,
         alk_dkh: Some(8.0),
+        ..Default::default()
     };
     let ass = Assumptions {
         ..Default::default()
@@ -27,7 +29,15 @@ fn estimates_chloride_close_to_reference_when_missing() {
     let (n_hco3, n_co3, n_oh, _alk_mg_l) =
         alk_species_from_dkh(ass.alkalinity.unwrap_or(8.0), ass.alk_mg_per_meq);
 
-    let cl_mg_l = estimate_cl_mg_l(&inputs, ass.default_f_mg_l, n_borate, n_hco3, n_co3, n_oh);
+    let ctx = EstimationContext {
+        default_f_mg_l: ass.default_f_mg_l,
+        n_borate,
+        n_hco3,
+        n_co3,
+        n_oh,
+        ..Default::default()
+    };
+    let cl_mg_l = estimate_cl_mg_l(&inputs, &ctx);
 
     // Seawater chloride (mg/L) at SP~35 typically ~19,000–21,000 mg/L depending on composition/density.
     assert!(
@@ -35,3 +45,462 @@ fn estimates_chloride_close_to_reference_when_missing() {
         "estimated Cl mg/L unexpected: {cl_mg_l}"
     );
 }
+
+#[test]
+fn solver_uses_supplied_chloride_estimator() {
+    use salinity_rs::chemistry::chloride::ChlorideEstimator;
+    use salinity_rs::{CalcResult, calc_salinity_sp_iterative_with_estimator};
+
+    struct Fixed(f64);
+    impl ChlorideEstimator for Fixed {
+        fn estimate(&self, _inp: &Inputs, _ctx: &EstimationContext) -> f64 {
+            self.0
+        }
+    }
+
+    let inputs = Inputs {
+        na: 11_980.0,
+        ca: 357.0,
+        mg: 1_246.0,
+        k: 464.0,
+        sr: 6.96,
+        br: 73.2,
+        cl: None,
+        f: Some(1.14),
+        s: 814.0,
+        b: 5.57,
+        alk_dkh: Some(8.0),
+        ..Default::default()
+    };
+    let ass = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+
+    let detailed = match calc_salinity_sp_iterative_with_estimator(
+        &inputs,
+        &ass,
+        30,
+        1e-8,
+        Some(&Fixed(12_345.0)),
+    ) {
+        CalcResult::Detailed(d) => d,
+        CalcResult::Simple(_) => panic!("expected detailed output"),
+    };
+    let cl = detailed
+        .components
+        .mg_l
+        .iter()
+        .find(|(name, _)| *name == "Cl-")
+        .map(|(_, v)| *v)
+        .unwrap();
+    assert!(
+        (cl - 12_345.0).abs() < 1e-9,
+        "solver ignored estimator: {cl}"
+    );
+}
+
+#[test]
+fn components_flag_chloride_as_estimated_only_when_missing() {
+    use salinity_rs::{CalcResult, calc_salinity_sp_teos10};
+
+    let measured = Inputs {
+        na: 11_980.0,
+        ca: 357.0,
+        mg: 1_246.0,
+        k: 464.0,
+        sr: 6.96,
+        br: 73.2,
+        cl: Some(19_570.0),
+        f: Some(1.14),
+        s: 814.0,
+        b: 5.57,
+        alk_dkh: Some(8.0),
+        ..Default::default()
+    };
+    let missing = Inputs {
+        cl: None,
+        ..measured.clone()
+    };
+    let ass = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+
+    let components = |inp: &Inputs| match calc_salinity_sp_teos10(inp, &ass, 30, 1e-8) {
+        CalcResult::Detailed(d) => d.components,
+        CalcResult::Simple(_) => panic!("expected detailed output"),
+    };
+
+    let c_missing = components(&missing);
+    assert!(c_missing.is_estimated("Cl-"));
+    assert!(!c_missing.is_estimated("Na+"));
+    assert!(!c_missing.is_estimated("F-"));
+
+    let c_measured = components(&measured);
+    assert!(!c_measured.is_estimated("Cl-"));
+    assert_eq!(c_measured.estimated.len(), c_measured.mg_l.len());
+}
+
+#[test]
+fn chloride_interval_widens_for_inconsistent_panels() {
+    let ass = Assumptions::default();
+    let (_n_boric, n_borate) = boron_partition(5.0, BORATE_FRACTION_DEFAULT);
+    let (n_hco3, n_co3, n_oh, _) = alk_species_from_dkh(8.0, None);
+    let ctx = EstimationContext {
+        default_f_mg_l: ass.default_f_mg_l,
+        n_borate,
+        n_hco3,
+        n_co3,
+        n_oh,
+        ..Default::default()
+    };
+    let rel_width = |inp: &Inputs| {
+        let (low, best, high) = estimate_cl_mg_l_interval(inp, &ctx);
+        assert!(low <= best && best <= high);
+        (high - low) / best
+    };
+
+    let consistent = Inputs {
+        cl: None,
+        ..salinity_rs::standard_seawater(35.0, &ass)
+    };
+    let inconsistent = Inputs {
+        mg: consistent.mg * 3.0,
+        k: consistent.k * 0.5,
+        ..consistent.clone()
+    };
+
+    let narrow = rel_width(&consistent);
+    let wide = rel_width(&inconsistent);
+    assert!(narrow < 0.05, "consistent band too wide: {narrow}");
+    assert!(wide > 0.5, "inconsistent band too narrow: {wide}");
+}
+
+#[test]
+fn charge_breakdown_itemizes_the_residual() {
+    let inputs = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+    let (n_hco3, n_co3, n_oh, _) = alk_species_from_dkh(8.0, None);
+    let (_, n_borate) = boron_partition(inputs.b, BORATE_FRACTION_DEFAULT);
+    let cl = inputs.cl.unwrap();
+    let ctx = EstimationContext {
+        default_f_mg_l: 1.3,
+        n_borate,
+        n_hco3,
+        n_co3,
+        n_oh,
+        ..Default::default()
+    };
+
+    let breakdown = charge_breakdown(&inputs, cl, &ctx);
+    let cations: f64 = breakdown.cations.iter().map(|(_, eq)| eq).sum();
+    let anions: f64 = breakdown.anions.iter().map(|(_, eq)| eq).sum();
+    assert_eq!(cations, breakdown.total_cations);
+    assert_eq!(anions, breakdown.total_anions);
+    assert_eq!(cations - anions, charge_balance_residual(&inputs, cl, &ctx));
+    // Standard seawater is close to electroneutral (~0.6 eq/L of each sign).
+    assert!(breakdown.residual.abs() < 0.01 * breakdown.total_cations);
+
+    // Without chloride the residual is exactly what the estimator assigns to Cl-.
+    let without_cl = charge_balance_residual(&inputs, 0.0, &ctx);
+    let estimate = estimate_cl_mg_l_from_charge_balance(&inputs, &ctx);
+    assert!((without_cl * M_CL * 1000.0 - estimate).abs() < 1e-9);
+}
+
+#[test]
+fn low_ph_removes_hf_from_the_fluoride_charge() {
+    use salinity_rs::chemistry::fluoride::{fluoride_anion_fraction, hf_dissociation_constant};
+
+    // Dickson & Riley (1979): pKF ≈ 2.6 on the free scale at 25 °C, S = 35.
+    let pkf = -hf_dissociation_constant(25.0, 35.0).log10();
+    assert!((2.5..2.7).contains(&pkf), "pKF {pkf}");
+
+    let f_charge = |ph: Option<f64>, temp_c: f64| {
+        let inputs = Inputs {
+            f: Some(1.3),
+            ph,
+            ..salinity_rs::standard_seawater(35.0, &Assumptions::default())
+        };
+        let ctx = EstimationContext {
+            default_f_mg_l: 1.3,
+            temp_c,
+            ..Default::default()
+        };
+        let breakdown = charge_breakdown(&inputs, 19_350.0, &ctx);
+        breakdown
+            .anions
+            .iter()
+            .find(|(name, _)| *name == "F-")
+            .unwrap()
+            .1
+    };
+    let unspeciated = f_charge(None, 25.0);
+    assert!((f_charge(Some(8.0), 25.0) / unspeciated - 1.0).abs() < 1e-4);
+    // HF is a fairly strong acid in seawater: pH 4 only removes a few percent,
+    // while near pH 2 most fluoride is undissociated.
+    let at_ph4 = f_charge(Some(4.0), 25.0) / unspeciated;
+    assert!((0.9..0.99).contains(&at_ph4), "F- share at pH 4: {at_ph4}");
+    assert!(f_charge(Some(2.0), 25.0) / unspeciated < 0.3);
+    assert!((at_ph4 - fluoride_anion_fraction(4.0, 25.0, 35.0)).abs() < 1e-12);
+}
+
+#[test]
+fn fluoride_charge_follows_the_sample_temperature() {
+    use salinity_rs::chemistry::fluoride::fluoride_anion_fraction;
+
+    let inputs = Inputs {
+        f: Some(1.3),
+        ph: Some(3.0),
+        ..salinity_rs::standard_seawater(35.0, &Assumptions::default())
+    };
+    let f_share = |temp_c: f64| {
+        let ctx = EstimationContext {
+            default_f_mg_l: 1.3,
+            temp_c,
+            ..Default::default()
+        };
+        let breakdown = charge_breakdown(&inputs, 19_350.0, &ctx);
+        let f_eq = breakdown
+            .anions
+            .iter()
+            .find(|(name, _)| *name == "F-")
+            .unwrap()
+            .1;
+        f_eq / mol_per_l(1.3, M_F)
+    };
+
+    // KF grows on cooling (the dissociation is exothermic), so a cold sample
+    // at pH 3 keeps more fluoride as F- than a warm one.
+    let cold = f_share(5.0);
+    let warm = f_share(25.0);
+    assert!((cold - fluoride_anion_fraction(3.0, 5.0, 35.0)).abs() < 1e-12);
+    assert!((warm - fluoride_anion_fraction(3.0, 25.0, 35.0)).abs() < 1e-12);
+    assert!(
+        cold - warm > 0.03,
+        "F- share {cold} at 5 °C vs {warm} at 25 °C"
+    );
+
+    // The solver passes `Assumptions::temp` through to the charge balance.
+    let missing_cl = Inputs { cl: None, ..inputs };
+    let cl_at = |temp: f64| {
+        let ass = Assumptions {
+            temp,
+            ..Default::default()
+        };
+        let detailed = salinity_rs::calc_all(&missing_cl, &ass, 50, 1e-10);
+        detailed
+            .components
+            .mg_l
+            .iter()
+            .find(|(k, _)| *k == "Cl-")
+            .unwrap()
+            .1
+    };
+    assert_ne!(cl_at(5.0), cl_at(25.0));
+}
+
+#[test]
+fn mass_closure_detects_missing_chloride() {
+    use salinity_rs::{Assumptions, Inputs, mass_closure_pct, standard_seawater};
+
+    let ass = Assumptions::default();
+    let seawater = standard_seawater(35.0, &ass);
+    let closure = mass_closure_pct(&seawater, &ass);
+    assert!((closure - 100.0).abs() < 1.0, "{closure}");
+
+    // The solver would estimate the missing Cl-, but that is not measured mass.
+    let no_cl = Inputs {
+        cl: None,
+        ..seawater
+    };
+    let closure = mass_closure_pct(&no_cl, &ass);
+    assert!((40.0..50.0).contains(&closure), "{closure}");
+}
+
+#[test]
+fn ratio_blend_weights_follow_reference_abundances() {
+    let weights = ratio_blend_weights();
+    let total: f64 = weights.iter().map(|&(_, w)| w).sum();
+    assert!((total - 1.0).abs() < 1e-12);
+
+    let weight = |name: &str| weights.iter().find(|(n, _)| *n == name).unwrap().1;
+    assert!((weight("Na+") / weight("Mg2+") - REF_MMOL_NA / REF_MMOL_MG).abs() < 1e-12);
+    assert!((weight("SO4^2-") / weight("K+") - REF_MMOL_SO4 / REF_MMOL_K).abs() < 1e-12);
+    assert_eq!(weights.len(), 7);
+    assert!(weights.iter().all(|&(n, _)| n != "Cl-" && n != "F-"));
+}
+
+#[test]
+fn robust_ratio_estimate_resists_an_outlying_ion() {
+    use salinity_rs::chemistry::chloride::{ChlorideEstimator, RatioBased, RatioRobust};
+    use salinity_rs::standard_seawater;
+
+    let panel = standard_seawater(35.0, &Assumptions::default());
+    let true_cl = panel.cl.unwrap();
+    // Calcium reported 100x too high (e.g. a unit slip).
+    let bad_ca = Inputs {
+        ca: panel.ca * 100.0,
+        cl: None,
+        ..panel
+    };
+    let ctx = EstimationContext::default();
+    let mean = RatioBased.estimate(&bad_ca, &ctx);
+    let robust = RatioRobust.estimate(&bad_ca, &ctx);
+
+    assert!(
+        (robust / true_cl - 1.0).abs() < 0.01,
+        "{robust} vs {true_cl}"
+    );
+    assert!(mean / true_cl > 1.5, "{mean} vs {true_cl}");
+}
+
+#[test]
+fn ion_equivalents_of_standard_seawater_balance() {
+    let inputs = Inputs {
+        alk_dkh: Some(6.2),
+        ..salinity_rs::standard_seawater(35.0, &Assumptions::default())
+    };
+    let table = ion_equivalents_meq_l(&inputs, inputs.cl.unwrap());
+    let sum = |names: &[&str]| -> f64 {
+        table
+            .iter()
+            .filter(|(n, _)| names.contains(n))
+            .map(|(_, v)| v)
+            .sum()
+    };
+    let cations = sum(&["Na+", "Mg2+", "Ca2+", "K+", "Sr2+"]);
+    let anions = sum(&["Cl-", "SO4^2-", "Br-", "F-", "Alk."]);
+    assert!((550.0..650.0).contains(&cations), "{cations} meq/L");
+    assert!(
+        (cations - anions).abs() < 0.01 * cations,
+        "{cations} vs {anions}"
+    );
+    assert!(table.iter().any(|(n, _)| *n == "Alk."));
+}
+
+#[test]
+fn solver_and_estimate_cl_mg_l_resolve_fluoride_alike() {
+    use salinity_rs::calc_all;
+
+    let inputs = Inputs {
+        na: 11_980.0,
+        ca: 357.0,
+        mg: 1_246.0,
+        k: 464.0,
+        sr: 6.96,
+        br: 73.2,
+        cl: None,
+        f: None,
+        s: 814.0,
+        b: 5.57,
+        alk_dkh: Some(8.0),
+        ..Default::default()
+    };
+    let ass = Assumptions {
+        default_f_mg_l: 2.5,
+        ..Default::default()
+    };
+    let row = |table: &[(&str, f64)], name: &str| {
+        table
+            .iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| *v)
+            .unwrap()
+    };
+
+    let detailed = calc_all(&inputs, &ass, 50, 1e-10);
+    let f = fluoride_mg_l(&inputs, ass.default_f_mg_l);
+    assert_eq!(f, 2.5);
+    assert!((row(&detailed.components.mg_l, "F-") - f).abs() < 1e-9);
+
+    let (_, n_borate) = boron_partition(inputs.b, BORATE_FRACTION_DEFAULT);
+    let (n_hco3, n_co3, n_oh, _) = alk_species_from_dkh(8.0, None);
+    let ctx = EstimationContext {
+        default_f_mg_l: ass.default_f_mg_l,
+        n_borate,
+        n_hco3,
+        n_co3,
+        n_oh,
+        ..Default::default()
+    };
+    let cl = estimate_cl_mg_l(&inputs, &ctx);
+    assert!((row(&detailed.components.mg_l, "Cl-") - cl).abs() < 1e-9);
+
+    // A measured value overrides the default on every path.
+    let measured = Inputs {
+        f: Some(1.0),
+        ..inputs
+    };
+    assert_eq!(fluoride_mg_l(&measured, ass.default_f_mg_l), 1.0);
+    let detailed = calc_all(&measured, &ass, 50, 1e-10);
+    assert!((row(&detailed.components.mg_l, "F-") - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn custom_cation_enters_charge_balance_and_mass_budget() {
+    use salinity_rs::{CustomIon, calc_all};
+
+    let base = Inputs {
+        cl: None,
+        ..salinity_rs::standard_seawater(35.0, &Assumptions::default())
+    };
+    let with_nh4 = Inputs {
+        custom: Some(vec![CustomIon {
+            name: "NH4+".into(),
+            mg_l: 18.038,
+            molar_mass: 18.038,
+            charge: 1,
+        }]),
+        ..base.clone()
+    };
+    let (_, n_borate) = boron_partition(base.b, BORATE_FRACTION_DEFAULT);
+    let (n_hco3, n_co3, n_oh, _) = alk_species_from_dkh(8.0, None);
+    let ctx = EstimationContext {
+        default_f_mg_l: 1.3,
+        n_borate,
+        n_hco3,
+        n_co3,
+        n_oh,
+        ..Default::default()
+    };
+    let cation = |inputs: &Inputs| {
+        let breakdown = charge_breakdown(inputs, 0.0, &ctx);
+        let custom = breakdown
+            .cations
+            .iter()
+            .find(|(name, _)| *name == "Custom+")
+            .unwrap()
+            .1;
+        (custom, breakdown.total_cations)
+    };
+    let (custom_base, total_base) = cation(&base);
+    let (custom_nh4, total_nh4) = cation(&with_nh4);
+    assert_eq!(custom_base, 0.0);
+    // 1 mmol/L of a monovalent cation.
+    assert!((custom_nh4 - 1e-3).abs() < 1e-12);
+    assert!((total_nh4 - total_base - 1e-3).abs() < 1e-12);
+
+    // An extra cation is balanced by more chloride: +1 meq/L of Cl- (35.45 mg/L)
+    // from the charge balance, and a higher blended estimate.
+    let by_charge = |inputs: &Inputs| estimate_cl_mg_l_from_charge_balance(inputs, &ctx);
+    assert!((by_charge(&with_nh4) - by_charge(&base) - M_CL).abs() < 1e-6);
+    let cl = |inputs: &Inputs| estimate_cl_mg_l(inputs, &ctx);
+    assert!(cl(&with_nh4) > cl(&base));
+
+    let ass = Assumptions::default();
+    let detailed = calc_all(&with_nh4, &ass, 50, 1e-10);
+    let row = detailed
+        .components
+        .mg_l
+        .iter()
+        .find(|(name, _)| *name == "Custom")
+        .unwrap();
+    assert!((row.1 - 18.038).abs() < 1e-9);
+    assert!(
+        calc_all(&base, &ass, 50, 1e-10)
+            .components
+            .mg_l
+            .iter()
+            .all(|(name, _)| *name != "Custom")
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "color")]
+
+fn run(inputs: serde_json::Value) -> String {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .arg("--color")
+        .arg("always")
+        .arg("--inputs-json")
+        .arg(inputs.to_string())
+        .arg("--assumptions-json")
+        .arg(r#"{"return_components": true}"#)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(out).unwrap()
+}
+
+fn sample(cl: Option<f64>) -> serde_json::Value {
+    serde_json::json!({
+        "na": 11980.0,
+        "ca": 357.0,
+        "mg": 1246.0,
+        "k": 464.0,
+        "sr": 6.96,
+        "br": 73.2,
+        "cl": cl,
+        "f": 1.14,
+        "s": 814.0,
+        "b": 5.57,
+        "alk_dkh": 8.0
+    })
+}
+
+#[test]
+fn ansi_codes_appear_only_with_warnings() {
+    let clean = run(sample(Some(19570.0)));
+    assert!(!clean.contains('\u{1b}'), "unexpected ANSI codes:\n{clean}");
+    assert!(!clean.contains("Warning:"));
+
+    let estimated = run(sample(None));
+    assert!(
+        estimated.contains("\u{1b}["),
+        "missing ANSI codes:\n{estimated}"
+    );
+    assert!(estimated.contains("Warning: Cl- was estimated"));
+}
@@ -126,3 +126,85 @@ fn cli_fails_when_computation_produces_non_finite_values() {
         .failure()
         .stderr(predicate::str::contains("non-finite"));
 }
+
+#[test]
+fn strict_json_rejects_misspelled_input_field() {
+    let inputs = serde_json::json!({
+        "sdoium": 11980.0,
+        "na": 11980.0,
+        "ca": 357.0,
+        "mg": 1246.0,
+        "k": 464.0,
+        "sr": 6.96,
+        "br": 73.2,
+        "cl": 19570.0,
+        "f": 1.14,
+        "s": 814.0,
+        "b": 5.57
+    })
+    .to_string();
+
+    let mut lenient = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    lenient
+        .arg("--json")
+        .arg("--inputs-json")
+        .arg(&inputs)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"sp\""));
+
+    let mut strict = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    strict
+        .arg("--json")
+        .arg("--strict-json")
+        .arg("--inputs-json")
+        .arg(&inputs)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown field `sdoium`"));
+}
+
+#[test]
+fn strict_json_rejects_unknown_assumption_in_document() {
+    let doc = serde_json::json!({
+        "inputs": {
+            "na": 11980.0,
+            "ca": 357.0,
+            "mg": 1246.0,
+            "k": 464.0,
+            "sr": 6.96,
+            "br": 73.2,
+            "cl": 19570.0,
+            "f": 1.14,
+            "s": 814.0,
+            "b": 5.57
+        },
+        "assumptions": { "temperature": 25.0 }
+    })
+    .to_string();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.arg("--strict-json")
+        .arg("--input")
+        .arg("-")
+        .write_stdin(doc)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown field `temperature`"));
+}
+
+#[test]
+fn app_error_json_carries_stable_kind() {
+    use salinity_rs::AppError;
+
+    let json = AppError::MissingInputData.to_error_json();
+    assert_eq!(json["kind"], "missing_input_data");
+    assert_eq!(
+        json["message"],
+        AppError::MissingInputData.to_string().as_str()
+    );
+    assert_eq!(
+        AppError::Other("boom".into()).to_error_json()["kind"],
+        "other"
+    );
+}
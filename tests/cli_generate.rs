@@ -0,0 +1,49 @@
+#![cfg(all(feature = "cli", feature = "rand"))]
+
+use salinity_rs::{Assumptions, Inputs, compute_summary};
+
+fn generate(seed: &str) -> String {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.args([
+        "generate",
+        "--count",
+        "5",
+        "--seed",
+        seed,
+        "--sp-range",
+        "30",
+        "36",
+    ]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn generate_emits_deterministic_documents_in_sp_range() {
+    let first = generate("1");
+    let second = generate("1");
+    assert_eq!(first, second, "same seed must reproduce the same documents");
+
+    let lines: Vec<&str> = first.lines().collect();
+    assert_eq!(lines.len(), 5);
+
+    for line in lines {
+        let doc: serde_json::Value = serde_json::from_str(line).unwrap();
+        let inputs: Inputs = serde_json::from_value(doc["inputs"].clone()).unwrap();
+        let summary = compute_summary(&inputs, &Assumptions::default());
+        assert!(
+            (29.99..=36.01).contains(&summary.sp),
+            "generated SP {} out of range",
+            summary.sp
+        );
+    }
+}
+
+#[test]
+fn generate_rejects_inverted_sp_range() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.args(["generate", "--sp-range", "36", "30"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid --sp-range"));
+}
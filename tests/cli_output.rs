@@ -0,0 +1,281 @@
+#![cfg(feature = "cli")]
+
+use predicates::prelude::*;
+
+fn sample_inputs(cl: Option<f64>) -> String {
+    serde_json::json!({
+        "na": 11980.0,
+        "ca": 357.0,
+        "mg": 1246.0,
+        "k": 464.0,
+        "sr": 6.96,
+        "br": 73.2,
+        "cl": cl,
+        "f": 1.14,
+        "s": 814.0,
+        "b": 5.57,
+        "alk_dkh": 8.0
+    })
+    .to_string()
+}
+
+#[test]
+fn cli_marks_estimated_chloride_in_component_table() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.arg("--inputs-json")
+        .arg(sample_inputs(None))
+        .arg("--assumptions-json")
+        .arg(r#"{"return_components": true}"#);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Cl-*"))
+        .stdout(predicate::str::contains("Na+*").not());
+}
+
+#[test]
+fn cli_does_not_mark_measured_chloride() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.arg("--inputs-json")
+        .arg(sample_inputs(Some(19570.0)))
+        .arg("--assumptions-json")
+        .arg(r#"{"return_components": true}"#);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Cl-"))
+        .stdout(predicate::str::contains("Cl-*").not());
+}
+
+#[test]
+fn cli_batch_echoes_sample_identifiers() {
+    let inputs: serde_json::Value = serde_json::from_str(&sample_inputs(Some(19570.0))).unwrap();
+    let ndjson = [
+        serde_json::json!({ "id": "cast-7", "timestamp": "2024-05-01T12:00:00Z", "inputs": inputs }),
+        serde_json::json!({ "inputs": inputs }),
+    ]
+    .iter()
+    .map(|doc| doc.to_string())
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .arg("--batch")
+        .arg("--input")
+        .arg("-")
+        .write_stdin(ndjson)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["id"], "cast-7");
+    assert_eq!(lines[0]["timestamp"], "2024-05-01T12:00:00Z");
+    assert!(lines[0]["sp"].is_number());
+    assert!(lines[1].get("id").is_none());
+}
+
+#[test]
+fn cli_batch_progress_goes_to_stderr() {
+    let inputs: serde_json::Value = serde_json::from_str(&sample_inputs(Some(19570.0))).unwrap();
+    let ndjson = vec![serde_json::json!({ "inputs": inputs }).to_string(); 4].join("\n");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let output = cmd
+        .args(["--batch", "--progress", "--input", "-"])
+        .write_stdin(ndjson)
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("progress:  25%"), "{stderr}");
+    assert!(stderr.contains("progress: 100%"), "{stderr}");
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 4);
+}
+
+#[test]
+fn cli_woce_format_emits_fixed_width_record() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .arg("--format")
+        .arg("woce")
+        .arg("--inputs-json")
+        .arg(sample_inputs(Some(19570.0)))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    let record = out.strip_suffix('\n').unwrap();
+
+    assert_eq!(record.len(), salinity_rs::adapters::cli::WOCE_RECORD_LEN);
+    let sp: f64 = record[0..10].trim().parse().unwrap();
+    assert!(
+        (30.0..40.0).contains(&sp),
+        "SP column: {:?}",
+        &record[0..10]
+    );
+    assert_eq!(record[20..28].trim(), "20.000");
+}
+
+#[test]
+fn cli_woce_record_reports_per_sample_conditions() {
+    let mut inputs: serde_json::Value =
+        serde_json::from_str(&sample_inputs(Some(19570.0))).unwrap();
+    inputs["t_c"] = serde_json::json!(5.0);
+    inputs["p_dbar"] = serde_json::json!(100.0);
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .arg("--format")
+        .arg("woce")
+        .arg("--inputs-json")
+        .arg(inputs.to_string())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let record = String::from_utf8(out).unwrap();
+
+    assert_eq!(record[20..28].trim(), "5.000");
+    assert_eq!(record[28..37].trim(), "100.0");
+}
+
+#[test]
+fn cli_honors_per_field_precision() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.arg("--inputs-json")
+        .arg(sample_inputs(Some(19570.0)))
+        .arg("--assumptions-json")
+        .arg(r#"{"precision": {"density": 1}}"#);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match(r"(?m)^Density: \d+\.\d kg/m\^3$").unwrap())
+        .stdout(predicate::str::is_match(r"(?m)^SP: \d+\.\d{4}$").unwrap());
+}
+
+#[test]
+fn cli_fields_filters_json_summary() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .args(["--json", "--fields", "sp", "--inputs-json"])
+        .arg(sample_inputs(Some(19570.0)))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let keys: Vec<&String> = json.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["sp"]);
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.args(["--json", "--fields", "sp,salinity", "--inputs-json"])
+        .arg(sample_inputs(Some(19570.0)))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'salinity'"))
+        .stderr(predicate::str::contains("density_kg_per_m3"));
+}
+
+#[test]
+fn cli_markdown_format_renders_aligned_tables() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .arg("--format")
+        .arg("markdown")
+        .arg("--inputs-json")
+        .arg(sample_inputs(Some(19570.0)))
+        .arg("--assumptions-json")
+        .arg(r#"{"return_components": true}"#)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+
+    let separators: Vec<&str> = out.lines().filter(|l| l.starts_with("|---")).collect();
+    assert_eq!(separators.len(), 2, "{out}");
+    assert!(
+        separators
+            .iter()
+            .all(|l| l.ends_with(":|") && l.chars().all(|c| matches!(c, '|' | '-' | ':')))
+    );
+    let cl = out
+        .lines()
+        .find(|l| l.starts_with("| Cl- "))
+        .expect("Cl- row");
+    assert!(cl.contains("19570.000"), "{cl}");
+    // Pipes line up within the component table.
+    let species = out.lines().find(|l| l.starts_with("| Species")).unwrap();
+    assert_eq!(species.len(), cl.len());
+}
+
+#[test]
+fn cli_batch_stats_of_identical_samples() {
+    let inputs: serde_json::Value = serde_json::from_str(&sample_inputs(Some(19570.0))).unwrap();
+    let ndjson = vec![serde_json::json!({ "inputs": inputs }).to_string(); 3].join("\n");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .args(["--batch", "--stats", "--input", "-"])
+        .write_stdin(ndjson)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+    let stats = &lines[3]["stats"];
+    assert_eq!(stats["count"], 3);
+    for key in ["sp", "sa", "density_kg_per_m3"] {
+        let value = lines[0][key].as_f64().unwrap();
+        let stat = &stats[key];
+        assert!(
+            (stat["mean"].as_f64().unwrap() - value).abs() < 1e-9,
+            "{key}: {stat}"
+        );
+        assert_eq!(stat["min"].as_f64().unwrap(), value);
+        assert_eq!(stat["max"].as_f64().unwrap(), value);
+        assert!(stat["stddev"].as_f64().unwrap() < 1e-9, "{key}: {stat}");
+    }
+}
+
+#[test]
+fn cli_json_with_units_wraps_values() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .args(["--json", "--with-units", "--inputs-json"])
+        .arg(sample_inputs(Some(19570.0)))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+    assert_eq!(json["density_kg_per_m3"]["unit"], "kg/m^3");
+    assert!(json["density_kg_per_m3"]["value"].as_f64().unwrap() > 1000.0);
+    assert_eq!(json["sp"]["unit"], "PSS-78");
+    assert_eq!(json["sg_20_20"]["unit"], "1");
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "sqlite")]
+
+#[test]
+fn cli_batch_writes_one_row_per_sample_to_sqlite() {
+    let ass = salinity_rs::Assumptions::default();
+    let inputs = [30.0, 35.0].map(|sp| salinity_rs::standard_seawater(sp, &ass));
+    let expected_sp = inputs
+        .each_ref()
+        .map(|inp| salinity_rs::compute_summary(inp, &ass).sp);
+    let ndjson = inputs
+        .iter()
+        .zip(["a", "b"])
+        .map(|(inp, id)| serde_json::json!({ "id": id, "inputs": inp }).to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("results.db");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    let out = cmd
+        .args(["--batch", "--input", "-", "--output", "sqlite"])
+        .arg(&db)
+        .write_stdin(ndjson)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(out.is_empty());
+
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    let rows: Vec<(String, f64)> = conn
+        .prepare("SELECT id, sp FROM results ORDER BY rowid")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0], ("a".to_owned(), expected_sp[0]));
+    assert_eq!(rows[1], ("b".to_owned(), expected_sp[1]));
+}
+
+#[test]
+fn cli_rejects_unknown_output_kind() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("salinity_rs");
+    cmd.args(["--batch", "--input", "-", "--output", "csv", "out.csv"])
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Unsupported --output kind 'csv'"));
+}
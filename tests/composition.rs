@@ -0,0 +1,175 @@
+use salinity_rs::chemistry::composition::{BoronParams, CompositionError, ReferenceComposition};
+use salinity_rs::chemistry::{BORATE_FRACTION_DEFAULT, boron_partition, sum_ref_gkg};
+
+#[test]
+fn default_composition_is_valid_and_matches_constants() {
+    let comp = ReferenceComposition::default().validated().unwrap();
+    assert_eq!(comp.sum_gkg(), sum_ref_gkg());
+    BoronParams::default().validate().unwrap();
+    assert_eq!(
+        BoronParams::default().partition(4.4),
+        boron_partition(4.4, BORATE_FRACTION_DEFAULT)
+    );
+}
+
+#[test]
+fn zero_chloride_molar_mass_is_rejected() {
+    let err = ReferenceComposition {
+        m_cl: 0.0,
+        ..Default::default()
+    }
+    .validated()
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        CompositionError::NonPositiveMolarMass { species: "Cl-", .. }
+    ));
+}
+
+#[test]
+fn invalid_boron_params_are_rejected() {
+    let negative_mass = BoronParams {
+        m_borate: -60.83,
+        ..Default::default()
+    };
+    assert!(negative_mass.validate().is_err());
+
+    let bad_fraction = BoronParams {
+        borate_fraction: 1.5,
+        ..Default::default()
+    };
+    assert!(matches!(
+        bad_fraction.validated(),
+        Err(CompositionError::FractionOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn seawater_screen_accepts_standard_and_rejects_pure_nacl() {
+    use salinity_rs::chemistry::looks_like_seawater;
+    use salinity_rs::{Assumptions, Inputs, Warning, compute_full, standard_seawater};
+
+    let ass = Assumptions::default();
+    let seawater = standard_seawater(35.0, &ass);
+    assert!(looks_like_seawater(&seawater));
+    assert!(
+        !compute_full(&seawater, &ass)
+            .warnings
+            .contains(&Warning::NotSeawater)
+    );
+
+    // 35 g/L NaCl brine: Na and Cl only.
+    let nacl = Inputs {
+        na: 13_770.0,
+        cl: Some(21_230.0),
+        ..Default::default()
+    };
+    assert!(!looks_like_seawater(&nacl));
+    assert!(
+        compute_full(&nacl, &ass)
+            .warnings
+            .contains(&Warning::NotSeawater)
+    );
+}
+
+#[test]
+fn zero_valued_ions_can_be_treated_as_missing() {
+    use salinity_rs::chemistry::{estimate_cl_mg_l_from_ratios, looks_like_seawater_with};
+    use salinity_rs::{Assumptions, Warning, compute_full, standard_seawater};
+
+    let ass = Assumptions::default();
+    let seawater = standard_seawater(35.0, &ass);
+
+    // A zero Br never becomes a zero chloride candidate.
+    let no_br = salinity_rs::Inputs {
+        br: 0.0,
+        ..seawater.clone()
+    };
+    let with_br = estimate_cl_mg_l_from_ratios(&seawater);
+    let without_br = estimate_cl_mg_l_from_ratios(&no_br);
+    assert!((without_br - with_br).abs() < 1e-6 * with_br);
+
+    // K reported as 0 (not measured) only flags the panel by default.
+    let no_k = salinity_rs::Inputs { k: 0.0, ..seawater };
+    assert!(!looks_like_seawater_with(&no_k, false));
+    assert!(looks_like_seawater_with(&no_k, true));
+    assert!(
+        compute_full(&no_k, &ass)
+            .warnings
+            .contains(&Warning::NotSeawater)
+    );
+    let lenient = Assumptions {
+        treat_zero_as_missing: true,
+        ..ass
+    };
+    assert!(
+        !compute_full(&no_k, &lenient)
+            .warnings
+            .contains(&Warning::NotSeawater)
+    );
+}
+
+#[test]
+fn gypsum_spike_is_diagnosed() {
+    use salinity_rs::chemistry::contamination::diagnose_contamination;
+    use salinity_rs::chemistry::{M_CA, M_S};
+    use salinity_rs::{Assumptions, Inputs, Warning, compute_full, standard_seawater};
+
+    let ass = Assumptions::default();
+    let seawater = standard_seawater(35.0, &ass);
+    assert!(diagnose_contamination(&seawater).is_empty());
+
+    // 8 mmol/L CaSO4 on top of ~10 mmol/L Ca and ~28 mmol/L SO4.
+    let spiked = Inputs {
+        ca: seawater.ca + 8.0 * M_CA,
+        s: seawater.s + 8.0 * M_S,
+        ..seawater
+    };
+    let hints = diagnose_contamination(&spiked);
+    assert_eq!(hints[0].salt, "gypsum");
+    assert!(hints[0].confidence > 0.5, "{hints:?}");
+    assert!(hints.iter().all(|h| h.salt != "halite"));
+
+    assert!(
+        !compute_full(&spiked, &ass)
+            .warnings
+            .iter()
+            .any(|w| matches!(w, Warning::PossibleContamination { .. }))
+    );
+    let warned = Assumptions {
+        warn_contamination: true,
+        ..ass
+    };
+    assert!(
+        compute_full(&spiked, &warned)
+            .warnings
+            .iter()
+            .any(|w| matches!(w, Warning::PossibleContamination { salt: "gypsum", .. }))
+    );
+}
+
+#[test]
+fn typed_concentrations_round_trip() {
+    use salinity_rs::chemistry::units::{MgPerL, MolPerL};
+    use salinity_rs::chemistry::{M_CL, M_SO4, mol_per_l};
+
+    for (mg_l, molar_mass) in [(19_350.0, M_CL), (2_712.0, M_SO4), (0.0, M_CL)] {
+        let n = MgPerL(mg_l).to_mol_per_l(molar_mass);
+        assert_eq!(n.0, mol_per_l(mg_l, molar_mass));
+        let back = n.to_mg_per_l(molar_mass);
+        assert!((back.0 - mg_l).abs() <= 1e-12 * mg_l.max(1.0), "{back:?}");
+    }
+    assert_eq!(MgPerL(-5.0).to_mol_per_l(M_CL), MolPerL(0.0));
+}
+
+#[test]
+fn known_species_lists_names_with_molar_masses() {
+    use salinity_rs::chemistry::{M_BORATE, M_CL, known_species, molar_mass};
+
+    let species = known_species();
+    assert!(species.contains(&("Cl-", M_CL)));
+    assert!(species.contains(&("B(OH)4-", M_BORATE)));
+    assert!(species.iter().all(|(_, m)| *m > 0.0));
+    assert_eq!(molar_mass("Cl-"), Some(M_CL));
+    assert_eq!(molar_mass("Alk."), None);
+}
@@ -0,0 +1,99 @@
+use salinity_rs::conductivity_ms_cm;
+
+#[test]
+fn standard_seawater_conductivity_at_15c() {
+    let c = conductivity_ms_cm(35.0, 15.0, 0.0);
+    assert!((c - 42.914).abs() < 0.01, "C(35, 15, 0) = {c} mS/cm");
+}
+
+#[test]
+fn sp_from_c_reads_standard_seawater_as_sp_35() {
+    use salinity_rs::{c_from_sp, sp_from_c};
+
+    let sp = sp_from_c(42.914, 15.0, 0.0);
+    assert!((sp - 35.0).abs() < 0.01, "SP = {sp}");
+
+    let c = c_from_sp(30.0, 22.0, 100.0);
+    assert!((sp_from_c(c, 22.0, 100.0) - 30.0).abs() < 1e-6);
+    assert_eq!(sp_from_c(-5.0, 15.0, 0.0), sp_from_c(0.0, 15.0, 0.0));
+}
+
+#[test]
+fn conductivity_rises_with_temperature() {
+    let c15 = conductivity_ms_cm(35.0, 15.0, 0.0);
+    let c25 = conductivity_ms_cm(35.0, 25.0, 0.0);
+    assert!(c25 > c15 + 5.0, "expected ~53 mS/cm at 25 °C, got {c25}");
+}
+
+#[test]
+fn sa_from_conductivity_matches_chained_steps() {
+    use salinity_rs::{
+        sa_from_conductivity, sa_from_sp, sa_from_sp_location, sp_from_conductivity,
+    };
+
+    let (t, p) = (12.0, 200.0);
+    // North Atlantic and central Baltic.
+    for (lon, lat) in [(-30.0, 45.0), (20.0, 57.0)] {
+        let r = 0.92;
+        let sp = sp_from_conductivity(r, t, p);
+        let chained = sa_from_sp_location(sp, p, lon, lat);
+        assert_eq!(sa_from_conductivity(r, t, p, lon, lat), chained);
+    }
+
+    let r = salinity_rs::adapters::teos10::r_from_sp(35.0, 15.0, 0.0);
+    let sp = sp_from_conductivity(r, 15.0, 0.0);
+    assert!((sp - 35.0).abs() < 1e-9, "round trip gave SP {sp}");
+    assert_eq!(sa_from_sp_location(sp, 0.0, -30.0, 45.0), sa_from_sp(sp));
+
+    // Baltic waters carry extra non-conservative solute at low SP.
+    let baltic = sa_from_sp_location(7.0, 0.0, 20.0, 57.0);
+    assert!((baltic - (0.087 + 7.0 * (35.16504 - 0.087) / 35.0)).abs() < 1e-12);
+    assert!(baltic > sa_from_sp(7.0));
+    assert!(sa_from_sp_location(35.0, 0.0, 0.0, 95.0).is_nan());
+}
+
+#[test]
+fn instrument_readings_for_standard_seawater() {
+    use salinity_rs::{Instrument, instrument_reading, specific_gravity};
+
+    let c = instrument_reading(35.0, Instrument::Conductivity, 15.0);
+    assert!((c - 42.9).abs() < 0.05, "{c} mS/cm");
+
+    let sg = instrument_reading(35.0, Instrument::Hydrometer, 25.0);
+    assert_eq!(sg, specific_gravity(35.0, 25.0, 0.0));
+
+    let n_sw = instrument_reading(35.0, Instrument::Refractometer, 20.0);
+    let n_pw = instrument_reading(0.0, Instrument::Refractometer, 20.0);
+    assert!((n_pw - 1.333).abs() < 1e-4, "{n_pw}");
+    assert!((n_sw - 1.3394).abs() < 2e-4, "{n_sw}");
+}
+
+#[test]
+fn sp_is_flat_across_temperature_only_for_in_situ_ratios() {
+    use salinity_rs::adapters::teos10::r_from_sp;
+    use salinity_rs::{sp_conductivity_temperature_sweep, sp_from_conductivity};
+
+    let temps = [10.0, 15.0, 20.0, 25.0, 30.0];
+
+    // The in-situ ratio of SP = 35 water at each temperature maps back to 35.
+    for &t in &temps {
+        let [(t_out, sp)] =
+            sp_conductivity_temperature_sweep(r_from_sp(35.0, t, 0.0), &[t], 0.0)[..]
+        else {
+            panic!("expected one point");
+        };
+        assert_eq!(t_out, t);
+        assert!((sp - 35.0).abs() < 1e-6, "SP {sp} at {t} °C");
+    }
+
+    // A ratio held fixed across temperature drifts: it is read as fresher
+    // water at higher temperature.
+    let sweep = sp_conductivity_temperature_sweep(1.0, &temps, 0.0);
+    assert_eq!(sweep.len(), temps.len());
+    for &(t, sp) in &sweep {
+        assert_eq!(sp, sp_from_conductivity(1.0, t, 0.0));
+    }
+    assert!((sweep[1].1 - 35.0).abs() < 1e-6);
+    assert!(sweep.windows(2).all(|w| w[1].1 < w[0].1), "{sweep:?}");
+    assert!(sweep[0].1 - sweep[4].1 > 5.0, "{sweep:?}");
+}
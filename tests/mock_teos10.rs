@@ -0,0 +1,65 @@
+//! Solver checks against the analytic `mock-teos10` equation of state.
+//! Run with `cargo test --features mock-teos10 --test mock_teos10`; the other
+//! suites pin real TEOS-10 values and are expected to fail under the mock.
+#![cfg(feature = "mock-teos10")]
+
+use salinity_rs::adapters::mock_teos10::{ALPHA, BETA, CT0, RHO0, SA0};
+use salinity_rs::chemistry::SR_REF;
+use salinity_rs::{
+    Assumptions, CalcResult, Inputs, OutputPrecision, calc_salinity_sp_iterative, rho_from_sp,
+    standard_seawater,
+};
+
+fn solve_sp(inp: &Inputs, ass: &Assumptions) -> f64 {
+    let ass = Assumptions {
+        return_components: false,
+        precision: OutputPrecision {
+            sp: 12,
+            ..Default::default()
+        },
+        ..ass.clone()
+    };
+    match calc_salinity_sp_iterative(inp, &ass, 50, 1e-12) {
+        CalcResult::Simple(sp) => sp,
+        CalcResult::Detailed(d) => d.sp,
+    }
+}
+
+#[test]
+fn mock_density_is_the_documented_linear_eos() {
+    let ass = Assumptions {
+        temp: 20.0,
+        pressure_dbar: 1000.0,
+        ..Default::default()
+    };
+    let sa = SR_REF;
+    let expected = RHO0 * (1.0 - ALPHA * (20.0 - CT0) + BETA * (sa - SA0));
+    assert!((rho_from_sp(35.0, &ass) - expected).abs() < 1e-9);
+}
+
+#[test]
+fn solver_converges_to_the_analytic_fixed_point() {
+    let ass = Assumptions {
+        temp: 20.0,
+        ..Default::default()
+    };
+    let panel = standard_seawater(35.0, &ass);
+    let sp0 = solve_sp(&panel, &ass);
+    assert!((sp0 - 35.0).abs() < 0.01, "{sp0}");
+
+    // The mass budget is linear in the concentrations, so scaling them by f
+    // scales SA·ρ(SA) by f; with a linear ρ = a + b·SA that is a quadratic
+    // in SA.
+    let f = 1.1;
+    let concentrated = Inputs {
+        dilution_factor: Some(f),
+        ..panel
+    };
+    let sa0 = sp0 * SR_REF / 35.0;
+    let a = RHO0 * (1.0 - ALPHA * (20.0 - CT0) - BETA * SA0);
+    let b = RHO0 * BETA;
+    let c = f * sa0 * (a + b * sa0);
+    let sa = (-a + (a * a + 4.0 * b * c).sqrt()) / (2.0 * b);
+    let got = solve_sp(&concentrated, &ass);
+    assert!((got - 35.0 * sa / SR_REF).abs() < 1e-8, "{got}");
+}
@@ -29,6 +29,7 @@ fn calculates_salinity_reasonable_without_explicit_cl() {
         s: 814.0,
         b: 5.57,
         alk_dkh: None,
+        ..Default::default()
     };
 
     let ass = Assumptions {
@@ -58,6 +59,7 @@ fn calculates_salinity_for_sample_inputs_within_reasonable_bounds() {
         s: 814.0,
         b: 5.57,
         alk_dkh: None,
+        ..Default::default()
     };
 
     let ass = Assumptions {
@@ -93,6 +95,7 @@ fn salinity_norm_changes_component_normalization_factor() {
         s: 814.0,
         b: 5.57,
         alk_dkh: None,
+        ..Default::default()
     };
 
     let ass35 = Assumptions {
@@ -132,6 +135,7 @@ fn summary_matches_reference_values_for_known_sample() {
         s: 814.0,
         b: 5.57,
         alk_dkh: None,
+        ..Default::default()
     };
     let ass = Assumptions {
         ..Default::default()
@@ -139,9 +143,1275 @@ fn summary_matches_reference_values_for_known_sample() {
 
     let summary = salinity_rs::compute_summary(&inputs, &ass);
 
-    approx_eq(summary.sp, 35.2417, 1e-4);
-    approx_eq(summary.sa, 35.407_879_719_085_71, 1e-9);
-    approx_eq(summary.density_kg_per_m3, 1_024.945_755_987_009_5, 1e-9);
-    approx_eq(summary.sg_20_20, 1.026_579_948_021_515_4, 1e-12);
-    approx_eq(summary.sg_25_25, 1.026_237_065_651_817_4, 1e-12);
+    approx_eq(summary.sp, 35.2416, 1e-4);
+    approx_eq(summary.sa, 35.407_779_247_542_855, 1e-9);
+    assert_eq!(summary.sa_anomaly_g_kg, 0.0);
+    approx_eq(summary.density_kg_per_m3, 1_024.949_575_198_526_6, 1e-9);
+    approx_eq(summary.sg_20_20, 1.026_789_915_701_247_2, 1e-12);
+    approx_eq(summary.sg_25_25, 1.026_555_854_967_397_7, 1e-12);
+}
+
+#[test]
+fn standard_seawater_round_trips_through_solver() {
+    let ass = Assumptions {
+        ..Default::default()
+    };
+    for target in [10.0, 35.0, 40.0] {
+        let inputs = salinity_rs::standard_seawater(target, &ass);
+        let sp = match calc_salinity_sp_teos10(&inputs, &ass, 30, 1e-8) {
+            CalcResult::Simple(v) => v,
+            CalcResult::Detailed(d) => d.sp,
+        };
+        approx_eq(sp, target, 0.01);
+    }
+}
+
+#[test]
+fn sub_zero_seawater_and_brine_give_physical_densities() {
+    // Near-freezing open-ocean seawater and a cold sea-ice brine.
+    for (temp, target_sp, rho_min, rho_max) in [
+        (-1.8, 35.0, 1_027.5, 1_029.0),
+        (-5.0, 70.0, 1_050.0, 1_065.0),
+    ] {
+        let ass = Assumptions {
+            temp,
+            ..Default::default()
+        };
+        let inputs = salinity_rs::standard_seawater(target_sp, &ass);
+        let summary = salinity_rs::compute_summary(&inputs, &ass);
+
+        approx_eq(summary.sp, target_sp, 0.05);
+        approx_in_range(summary.density_kg_per_m3, rho_min, rho_max);
+        assert!(summary.sg_20_20.is_finite() && summary.sg_25_25.is_finite());
+    }
+}
+
+#[test]
+fn temperatures_below_the_domain_are_reported_not_clamped() {
+    use salinity_rs::{Warning, compute_full};
+
+    let inputs = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+    let ass = Assumptions {
+        temp: -8.0,
+        ..Default::default()
+    };
+
+    // No silent fallback to the −6 °C bound or a nominal density.
+    let summary = salinity_rs::compute_summary(&inputs, &ass);
+    assert!(summary.density_kg_per_m3.is_nan(), "{summary:?}");
+
+    let full = compute_full(&inputs, &ass);
+    assert!(
+        full.warnings.iter().any(|w| matches!(
+            w,
+            Warning::OutOfDomain {
+                quantity: "temperature"
+            }
+        )),
+        "{:?}",
+        full.warnings
+    );
+}
+
+#[test]
+fn sp_from_density_inverts_rho_from_sp() {
+    let ass = Assumptions {
+        temp: 25.0,
+        ..Default::default()
+    };
+    for sp in [5.0, 35.0, 60.0] {
+        let rho = rho_from_sp(sp, &ass);
+        let back = salinity_rs::sp_from_density(rho, &ass).expect("density within range");
+        approx_eq(back, sp, 1e-6);
+    }
+    assert!(salinity_rs::sp_from_density(900.0, &ass).is_none());
+}
+
+#[test]
+fn summary_reports_values_normalized_to_salinity_norm() {
+    let ass = Assumptions {
+        salinity_norm: 35.0,
+        ..Default::default()
+    };
+    let inputs = salinity_rs::standard_seawater(33.0, &ass);
+    let summary = salinity_rs::compute_summary(&inputs, &ass);
+
+    approx_eq(summary.sp, 33.0, 0.01);
+    approx_eq(summary.normalized_sp, 35.0, 1e-3);
+
+    let na_norm = summary
+        .normalized_ion_table
+        .iter()
+        .find(|(name, _)| *name == "Na+")
+        .map(|(_, v)| *v)
+        .unwrap();
+    approx_eq(na_norm, inputs.na * 35.0 / summary.sp, 0.5);
+}
+
+#[test]
+fn temperature_sweep_lowers_density_while_sp_stays_stable() {
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    let sweep = salinity_rs::summary_sweep_temperature(&inputs, &ass, &[10.0, 20.0, 30.0]);
+
+    assert_eq!(sweep.len(), 3);
+    for pair in sweep.windows(2) {
+        assert!(pair[1].density_kg_per_m3 < pair[0].density_kg_per_m3);
+        approx_eq(pair[1].sp, pair[0].sp, 0.5);
+    }
+}
+
+#[test]
+fn pressure_sweep_raises_density_while_sp_stays_stable() {
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    let sweep = salinity_rs::summary_sweep_pressure(&inputs, &ass, &[0.0, 1_000.0, 4_000.0]);
+
+    assert_eq!(sweep.len(), 3);
+    approx_eq(
+        sweep[0].density_kg_per_m3,
+        salinity_rs::compute_summary(&inputs, &ass).density_kg_per_m3,
+        1e-9,
+    );
+    for pair in sweep.windows(2) {
+        assert!(pair[1].density_kg_per_m3 > pair[0].density_kg_per_m3);
+        assert_eq!(pair[1].sp, pair[0].sp);
+    }
+}
+
+#[test]
+fn detailed_result_reports_sulfate_conversion() {
+    use salinity_rs::chemistry::{M_S, M_SO4, SulfateSource};
+
+    let inputs = Inputs {
+        na: 11_980.0,
+        ca: 357.0,
+        mg: 1_246.0,
+        k: 464.0,
+        sr: 6.96,
+        br: 73.2,
+        cl: Some(19_570.0),
+        f: Some(1.14),
+        s: 814.0,
+        b: 5.57,
+        alk_dkh: None,
+        ..Default::default()
+    };
+    let ass = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+
+    let detailed = |inp: &Inputs| match calc_salinity_sp_teos10(inp, &ass, 30, 1e-8) {
+        CalcResult::Detailed(d) => d,
+        CalcResult::Simple(_) => panic!("expected detailed output"),
+    };
+
+    let from_s = detailed(&inputs);
+    assert_eq!(from_s.so4_source, SulfateSource::FromSulfur);
+    approx_eq(from_s.so4_mg_l, 814.0 * M_SO4 / M_S, 1e-9);
+
+    let direct = detailed(&Inputs {
+        so4: Some(from_s.so4_mg_l),
+        ..inputs.clone()
+    });
+    assert_eq!(direct.so4_source, SulfateSource::Sulfate);
+    approx_eq(direct.sp, from_s.sp, 1e-9);
+}
+
+#[test]
+fn conflicting_s_and_so4_are_flagged() {
+    use salinity_rs::Warning;
+    use salinity_rs::chemistry::{M_S, M_SO4, reconcile_sulfate};
+
+    let ass = Assumptions::default();
+    let panel = salinity_rs::standard_seawater(35.0, &ass);
+    let equivalent = Inputs {
+        so4: Some(panel.s * M_SO4 / M_S),
+        ..panel.clone()
+    };
+    let (so4, warning) = reconcile_sulfate(&equivalent);
+    approx_eq(so4, panel.s * M_SO4 / M_S, 1e-9);
+    assert!(warning.is_none());
+
+    // Sulfate entered in both fields: SO4 from `s` is ~3x the reported SO4.
+    let doubled = Inputs {
+        s: so4,
+        so4: Some(so4),
+        ..panel
+    };
+    let (used, warning) = reconcile_sulfate(&doubled);
+    assert_eq!(used, so4);
+    assert!(matches!(warning, Some(Warning::SulfateConflict { .. })));
+    assert!(
+        salinity_rs::compute_full(&doubled, &ass)
+            .warnings
+            .iter()
+            .any(|w| matches!(w, Warning::SulfateConflict { .. }))
+    );
+    assert!(
+        !salinity_rs::compute_full(&equivalent, &ass)
+            .warnings
+            .iter()
+            .any(|w| matches!(w, Warning::SulfateConflict { .. }))
+    );
+}
+
+#[test]
+fn nutrient_correction_adds_small_positive_delta_sa() {
+    use salinity_rs::chemistry::sa_nutrient_correction;
+
+    assert_eq!(sa_nutrient_correction(0.0, 0.0, 0.0), 0.0);
+
+    // Typical deep North Pacific nutrients (µmol/kg).
+    let delta = sa_nutrient_correction(150.0, 40.0, 2.8);
+    approx_in_range(delta, 0.001, 0.05);
+
+    let ass = Assumptions::default();
+    let surface = salinity_rs::standard_seawater(35.0, &ass);
+    let deep = Inputs {
+        si_umol_kg: Some(150.0),
+        no3_umol_kg: Some(40.0),
+        po4_umol_kg: Some(2.8),
+        ..surface.clone()
+    };
+    let s0 = salinity_rs::compute_summary(&surface, &ass);
+    let s1 = salinity_rs::compute_summary(&deep, &ass);
+    approx_eq(s1.sa_anomaly_g_kg, delta, 1e-12);
+    approx_eq(s1.sa - s0.sa, delta, 1e-12);
+}
+
+#[test]
+fn sodium_and_chloride_dominate_sp_sensitivity() {
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    let sens = salinity_rs::sp_sensitivities(&inputs, &ass);
+
+    let get = |name: &str| sens.iter().find(|(n, _)| *n == name).unwrap().1;
+    let (na, cl) = (get("na").abs(), get("cl").abs());
+    for (name, d) in &sens {
+        if *name != "na" && *name != "cl" {
+            assert!(d.abs() < na.min(cl), "{name} sensitivity {d} exceeds Na/Cl");
+        }
+    }
+    // Cl is ~55 % of the dissolved mass, so a relative change moves SP by ~0.55·SP.
+    approx_in_range(cl, 18.0, 21.0);
+}
+
+#[test]
+fn minimal_ion_set_keeps_the_dominant_ions() {
+    use salinity_rs::Species;
+
+    // Na is both the largest cation and the main ratio proxy for dropped Cl.
+    let one_pct = salinity_rs::minimal_ion_set(0.01);
+    assert!(one_pct.contains(&Species::Na), "{one_pct:?}");
+    assert!(one_pct.len() < 10, "nothing dropped at 1 %: {one_pct:?}");
+
+    let strict = salinity_rs::minimal_ion_set(1e-6);
+    assert!(strict.len() > one_pct.len(), "{strict:?} vs {one_pct:?}");
+}
+
+#[test]
+fn dropping_chloride_matters_less_than_dropping_sodium() {
+    use salinity_rs::{Species, sp_without_ion};
+
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    let full = salinity_rs::salinity_ratio(&inputs, &ass) * 35.0 / 35.16504;
+
+    // A dropped Cl- is re-estimated from the other ions; a dropped Na+ is
+    // simply missing mass.
+    let without_cl = sp_without_ion(&inputs, &ass, Species::Cl);
+    let without_na = sp_without_ion(&inputs, &ass, Species::Na);
+    assert!(
+        (without_cl - full).abs() < (without_na - full).abs(),
+        "Cl: {without_cl}, Na: {without_na}, full: {full}"
+    );
+    assert!(without_na < full - 5.0, "{without_na}");
+}
+
+#[test]
+fn estimated_sp_bias_is_zero_for_a_complete_panel() {
+    use salinity_rs::estimated_sp_bias;
+
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    approx_eq(estimated_sp_bias(&inputs, &ass), 0.0, 1e-9);
+
+    // An estimated Cl- lands close to, but not exactly on, the standard ratio.
+    let no_cl = Inputs {
+        cl: None,
+        ..inputs.clone()
+    };
+    let bias = estimated_sp_bias(&no_cl, &ass);
+    assert!(bias != 0.0 && bias.abs() < 0.5, "bias {bias}");
+}
+
+#[test]
+fn compute_full_reports_convergence_and_summary() {
+    let inputs = Inputs {
+        na: 11_980.0,
+        ca: 357.0,
+        mg: 1_246.0,
+        k: 464.0,
+        sr: 6.96,
+        br: 73.2,
+        cl: Some(19_570.0),
+        f: Some(1.14),
+        s: 814.0,
+        b: 5.57,
+        alk_dkh: None,
+        ..Default::default()
+    };
+    let ass = Assumptions::default();
+
+    let full = salinity_rs::compute_full(&inputs, &ass);
+
+    assert!(full.converged);
+    assert!(full.iterations >= 1);
+    approx_eq(full.summary.sp, 35.2416, 1e-4);
+    assert!(!full.summary.normalized_ion_table.is_empty());
+    // Alkalinity comes from the assumption default, so it is flagged.
+    assert_eq!(
+        full.warnings,
+        vec![salinity_rs::Warning::Estimated { species: "Alk." }]
+    );
+    assert!(full.components.is_none());
+}
+
+#[test]
+fn compressibility_falls_with_pressure_and_matches_density_derivative() {
+    use salinity_rs::adapters::teos10::{kappa, rho};
+
+    let surface = Assumptions::default();
+    let deep = Assumptions {
+        pressure_dbar: 2_000.0,
+        ..Default::default()
+    };
+    let kappa0 = salinity_rs::compressibility(35.0, &surface);
+    assert!(salinity_rs::compressibility(35.0, &deep) < kappa0);
+
+    // κ = ∂ln ρ/∂P at fixed SA and CT (isentropic): central difference
+    // over ±1 dbar.
+    let (sa, ct, p) = (35.165_04, 20.0, 1_000.0);
+    let h = 1.0;
+    let fd = (rho(sa, ct, p + h).ln() - rho(sa, ct, p - h).ln()) / (2.0 * h * 1e4);
+    let k = kappa(sa, ct, p);
+    approx_eq(k, fd, 1e-4 * fd);
+    // Seawater at 20 °C and 1000 dbar: κ ≈ 4.2e-10 1/Pa.
+    approx_in_range(k, 4.0e-10, 4.4e-10);
+}
+
+#[test]
+fn detailed_result_exposes_salinity_ratio() {
+    let inputs = Inputs {
+        na: 11_980.0,
+        ca: 357.0,
+        mg: 1_246.0,
+        k: 464.0,
+        sr: 6.96,
+        br: 73.2,
+        cl: Some(19_570.0),
+        f: Some(1.14),
+        s: 814.0,
+        b: 5.57,
+        ..Default::default()
+    };
+    let ass = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+
+    let CalcResult::Detailed(d) = calc_salinity_sp_teos10(&inputs, &ass, 30, 1e-8) else {
+        panic!("expected detailed result");
+    };
+    let sr_ref = salinity_rs::chemistry::SR_REF;
+    approx_eq(d.sr, d.sp * sr_ref / 35.0, 1e-4);
+    approx_eq(salinity_rs::salinity_ratio(&inputs, &ass), d.sr, 1e-9);
+}
+
+#[test]
+fn sa_normalized_table_tracks_absolute_salinity() {
+    let ass = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+    let native = salinity_rs::standard_seawater(35.0, &ass);
+    let with_nutrients = Inputs {
+        si_umol_kg: Some(150.0),
+        no3_umol_kg: Some(40.0),
+        po4_umol_kg: Some(2.8),
+        ..native.clone()
+    };
+
+    let components = |inp: &Inputs| match calc_salinity_sp_teos10(inp, &ass, 30, 1e-8) {
+        CalcResult::Detailed(d) => (d.sr, d.components),
+        CalcResult::Simple(_) => panic!("expected detailed result"),
+    };
+
+    // Without nutrients SA = SR, so SR_REF / SA equals 35 / SP.
+    let (_, c) = components(&native);
+    approx_eq(c.norm_factor_sa, c.norm_factor, 1e-9);
+
+    // The nutrient anomaly raises SA above SR, shrinking the SA-normalized table.
+    let (sr, c) = components(&with_nutrients);
+    let anomaly = salinity_rs::compute_summary(&with_nutrients, &ass).sa_anomaly_g_kg;
+    assert!(anomaly > 0.0);
+    let expected = sr / (sr + anomaly);
+    for ((name, sa_norm), (_, sp_norm)) in c.mgkg_sa_ref.iter().zip(&c.mgkg_sp35) {
+        approx_eq(sa_norm / sp_norm, expected, 1e-9);
+        assert!(sa_norm < sp_norm, "{name}");
+    }
+}
+
+#[test]
+fn in_air_specific_gravity_applies_buoyancy_offset() {
+    let vacuum = specific_gravity(35.0, 20.0, 0.0);
+    let in_air = salinity_rs::specific_gravity_in_air(35.0, 20.0, 20.0);
+
+    let rho_air = salinity_rs::salinity::calculator::AIR_DENSITY_KG_M3;
+    let rho_pw = rho_from_sp(35.0, &Assumptions::default()) / vacuum;
+    let expected_offset = (vacuum - 1.0) * rho_air / (rho_pw - rho_air);
+    approx_eq(in_air - vacuum, expected_offset, 1e-12);
+    approx_in_range(in_air - vacuum, 2e-5, 4e-5);
+
+    approx_eq(
+        salinity_rs::specific_gravity_air_corrected(35.0, 20.0, 20.0, 0.0),
+        vacuum,
+        1e-15,
+    );
+}
+
+#[test]
+fn fixed_sp_bypasses_the_solver() {
+    let ass = Assumptions {
+        fixed_sp: Some(35.0),
+        return_components: true,
+        ..Default::default()
+    };
+    let brackish = salinity_rs::standard_seawater(12.0, &Assumptions::default());
+    let hypersaline = salinity_rs::standard_seawater(60.0, &Assumptions::default());
+
+    for inputs in [&brackish, &hypersaline] {
+        assert_eq!(salinity_rs::compute_summary(inputs, &ass).sp, 35.0);
+        let CalcResult::Detailed(d) = calc_salinity_sp_teos10(inputs, &ass, 30, 1e-8) else {
+            panic!("expected detailed result");
+        };
+        assert_eq!(d.sp, 35.0);
+        approx_eq(d.rho_kg_m3, rho_from_sp(35.0, &ass), 1e-9);
+    }
+    let full = salinity_rs::compute_full(&brackish, &ass);
+    assert!(full.converged);
+    assert_eq!(full.iterations, 0);
+}
+
+#[test]
+fn knudsen_and_pss78_agree_at_35_and_diverge_when_fresh() {
+    use salinity_rs::salinity::scales::{knudsen_salinity, sp_from_knudsen};
+
+    // Check value: Cl = 19.374 ‰ corresponds to S = 35 on both scales.
+    approx_eq(knudsen_salinity(19.374), 35.0, 1e-3);
+    approx_eq(sp_from_knudsen(19.374), 35.0, 1e-3);
+
+    // At Cl = 1 ‰ Knudsen's 0.030 intercept dominates the difference.
+    let diff = knudsen_salinity(1.0) - sp_from_knudsen(1.0);
+    approx_eq(diff, 0.030 - 0.00155, 1e-9);
+}
+
+#[test]
+fn salt_mass_scales_with_volume() {
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+
+    let one_m3 = salinity_rs::salt_mass_kg(&inputs, &ass, 1_000.0);
+    approx_eq(
+        salinity_rs::salt_mass_kg(&inputs, &ass, 2_000.0),
+        2.0 * one_m3,
+        1e-9,
+    );
+    // ~35 g/kg at ~1.025 kg/L.
+    approx_in_range(one_m3, 35.0, 37.0);
+}
+
+#[test]
+fn mmol_per_liter_panel_matches_mg_per_liter_panel() {
+    use salinity_rs::ConcentrationUnit;
+    use salinity_rs::chemistry::*;
+
+    let mg_l = Inputs {
+        na: 11_980.0,
+        ca: 357.0,
+        mg: 1_246.0,
+        k: 464.0,
+        sr: 6.96,
+        br: 73.2,
+        cl: Some(19_570.0),
+        f: Some(1.14),
+        s: 814.0,
+        b: 5.57,
+        alk_dkh: Some(8.0),
+        ..Default::default()
+    };
+    let mmol_l = Inputs {
+        na: mg_l.na / M_NA,
+        ca: mg_l.ca / M_CA,
+        mg: mg_l.mg / M_MG,
+        k: mg_l.k / M_K,
+        sr: mg_l.sr / M_SR,
+        br: mg_l.br / M_BR,
+        cl: mg_l.cl.map(|v| v / M_CL),
+        f: mg_l.f.map(|v| v / M_F),
+        s: mg_l.s / M_S,
+        b: mg_l.b / M_B,
+        ..mg_l.clone()
+    };
+    let ass = Assumptions::default();
+    let ass_mmol = Assumptions {
+        input_units: ConcentrationUnit::MmolPerL,
+        ..Default::default()
+    };
+
+    let expected = salinity_rs::compute_summary(&mg_l, &ass).sp;
+    approx_eq(
+        salinity_rs::compute_summary(&mmol_l, &ass_mmol).sp,
+        expected,
+        1e-9,
+    );
+    assert!(salinity_rs::compute_summary(&mmol_l, &ass).sp < 5.0);
+}
+
+#[test]
+fn blending_seawater_with_pure_water_halves_sp_at_half_ratio() {
+    let ass = Assumptions::default();
+    let stock = salinity_rs::standard_seawater(35.0, &ass);
+    let pure_water = Inputs {
+        cl: Some(0.0),
+        f: Some(0.0),
+        alk_dkh: Some(0.0),
+        ..Default::default()
+    };
+
+    let ratio = salinity_rs::blend_ratio_for_target_sp(&stock, &pure_water, 17.5, &ass).unwrap();
+    approx_in_range(ratio, 0.48, 0.52);
+    let blended = salinity_rs::mix(&stock, &pure_water, ratio);
+    approx_eq(salinity_rs::compute_summary(&blended, &ass).sp, 17.5, 1e-3);
+
+    assert!(salinity_rs::blend_ratio_for_target_sp(&stock, &pure_water, 40.0, &ass).is_none());
+}
+
+#[test]
+fn compute_full_flags_density_outside_teos10_domain() {
+    let ass = Assumptions {
+        pressure_dbar: 1e300,
+        ..Default::default()
+    };
+    let inputs = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+    let full = salinity_rs::compute_full(&inputs, &ass);
+
+    assert!(full.summary.density_kg_per_m3.is_nan());
+    assert!(full.warnings.contains(&salinity_rs::Warning::OutOfDomain {
+        quantity: "density"
+    }));
+}
+
+#[test]
+fn specific_gravity_guards_failed_pure_water_density() {
+    use salinity_rs::salinity::calculator::SG_PURE_WATER_RHO_EPSILON;
+    use salinity_rs::specific_gravity_from_densities;
+
+    let eps = SG_PURE_WATER_RHO_EPSILON;
+    approx_eq(
+        specific_gravity_from_densities(1_025.0, 1_000.0, eps),
+        1.025,
+        1e-12,
+    );
+    assert!(specific_gravity_from_densities(1_025.0, 1e-30, eps).is_nan());
+    assert!(specific_gravity_from_densities(1_025.0, f64::NAN, eps).is_nan());
+    assert!(specific_gravity_from_densities(1_025.0, 0.0, eps).is_nan());
+
+    let ass = Assumptions {
+        pressure_dbar: 1e300,
+        sg_at_sample_pressure: true,
+        ..Default::default()
+    };
+    let inputs = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+    let full = salinity_rs::compute_full(&inputs, &ass);
+    assert!(full.summary.sg_20_20.is_nan());
+    assert!(full.warnings.contains(&salinity_rs::Warning::OutOfDomain {
+        quantity: "specific gravity"
+    }));
+}
+
+#[test]
+fn brine_eos_stays_physical_beyond_teos10_range() {
+    use salinity_rs::EosKind;
+    use salinity_rs::adapters::brine::rho_brine;
+
+    let brine = Assumptions {
+        eos: EosKind::BrineExtended,
+        ..Default::default()
+    };
+    // Agrees with TEOS-10 to ~0.1 % inside the oceanographic range.
+    let teos = rho_from_sp(35.0, &Assumptions::default());
+    approx_eq(rho_from_sp(35.0, &brine), teos, 1e-3 * teos);
+
+    // At SA = 100 g/kg the correlation gives a physical brine density, and
+    // unlike the extrapolated TEOS-10 polynomial it keeps rising with SA.
+    let rho_100 = rho_brine(100.0, 20.0);
+    approx_in_range(rho_100, 1_065.0, 1_085.0);
+    assert!(rho_brine(150.0, 20.0) > rho_brine(120.0, 20.0));
+    assert!(rho_brine(200.0, 20.0).is_nan());
+
+    let hypersaline = salinity_rs::standard_seawater(100.0, &brine);
+    let sp = salinity_rs::compute_summary(&hypersaline, &brine).sp;
+    approx_eq(sp, 100.0, 0.05);
+}
+
+#[test]
+fn gh_is_apportioned_into_ca_and_mg() {
+    use salinity_rs::chemistry::{DGH_TO_MMOL_L, M_CA, M_MG, REF_MMOL_CA, REF_MMOL_MG};
+
+    let ass = Assumptions::default();
+    let reference = salinity_rs::standard_seawater(35.0, &ass);
+    let hardness_mmol = reference.ca / M_CA + reference.mg / M_MG;
+    let gh_only = Inputs {
+        ca: 0.0,
+        mg: 0.0,
+        gh_dgh: Some(hardness_mmol / DGH_TO_MMOL_L),
+        ..reference.clone()
+    };
+
+    let split = gh_only.with_gh_apportioned(REF_MMOL_CA / REF_MMOL_MG);
+    approx_eq(split.ca / M_CA + split.mg / M_MG, hardness_mmol, 1e-9);
+    approx_eq(split.ca, reference.ca, 0.01 * reference.ca);
+    approx_eq(split.mg, reference.mg, 0.01 * reference.mg);
+
+    // Given Mg, GH fills Ca with the remainder.
+    let with_mg = Inputs {
+        mg: reference.mg,
+        ..gh_only.clone()
+    }
+    .with_gh_apportioned(1.0);
+    approx_eq(with_mg.ca, reference.ca, 0.01 * reference.ca);
+
+    let sp = salinity_rs::compute_summary(&gh_only, &ass).sp;
+    approx_eq(sp, 35.0, 0.05);
+}
+
+#[test]
+fn echo_config_reports_effective_reference_composition() {
+    let inputs = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+    assert!(
+        salinity_rs::compute_full(&inputs, &Assumptions::default())
+            .config_used
+            .is_none()
+    );
+
+    let ass = Assumptions {
+        rn_compat: true,
+        assume_borate: false,
+        echo_config: true,
+        ..Default::default()
+    };
+    let config = salinity_rs::compute_full(&inputs, &ass)
+        .config_used
+        .expect("echo_config set");
+    assert_eq!(config.ref_alk_dkh, Some(6.2));
+    assert_eq!(config.assumptions.ref_alk_dkh, Some(6.2));
+    assert_eq!(config.borate_fraction, 0.0);
+    approx_eq(config.sr_ref, 35.16504, 1e-12);
+}
+
+#[test]
+fn salinity_lut_matches_direct_density_inversion() {
+    use salinity_rs::{build_salinity_lut, sp_from_density};
+
+    let ass = Assumptions::default();
+    let lut = build_salinity_lut((0.0, 45.0), (10.0, 30.0), 46, &ass);
+
+    for (sp, temp) in [
+        (5.3, 12.4),
+        (20.0, 25.0),
+        (33.7, 18.2),
+        (35.0, 26.5),
+        (44.1, 29.9),
+    ] {
+        let at_t = Assumptions {
+            temp,
+            ..ass.clone()
+        };
+        let rho = rho_from_sp(sp, &at_t);
+        let direct = sp_from_density(rho, &at_t).expect("inside solver range");
+        // 1 SP x 0.4 °C cells: interpolation error stays below ~5e-4.
+        approx_eq(lut.lookup_sp(rho, temp), direct, 2e-3);
+    }
+    assert!(lut.lookup_sp(1_020.0, 40.0).is_nan());
+    assert!(lut.lookup_sp(1_100.0, 20.0).is_nan());
+}
+
+#[test]
+fn freezing_margin_tracks_distance_to_freezing() {
+    use salinity_rs::freezing_margin;
+
+    let warm = Assumptions {
+        temp: 25.0,
+        ..Default::default()
+    };
+    // Seawater freezes near -1.92 °C, so 25 °C is ~27 K above freezing.
+    approx_eq(freezing_margin(35.0, &warm), 26.9, 0.1);
+
+    let near_freezing = Assumptions {
+        temp: -1.9,
+        ..Default::default()
+    };
+    let margin = freezing_margin(35.0, &near_freezing);
+    assert!(margin.abs() < 0.05, "margin {margin}");
+    // Fresher water freezes at a higher temperature.
+    assert!(freezing_margin(5.0, &near_freezing) < margin);
+}
+
+#[test]
+fn sg_at_sample_pressure_tracks_deep_samples() {
+    let surface_sg = Assumptions {
+        pressure_dbar: 1000.0,
+        ..Default::default()
+    };
+    let in_situ_sg = Assumptions {
+        sg_at_sample_pressure: true,
+        ..surface_sg.clone()
+    };
+    let inputs = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+
+    let surface = salinity_rs::compute_summary(&inputs, &surface_sg);
+    let deep = salinity_rs::compute_summary(&inputs, &in_situ_sg);
+    assert_eq!(deep.sp, surface.sp);
+    assert_eq!(surface.sg_20_20, specific_gravity(surface.sp, 20.0, 0.0));
+    assert_eq!(deep.sg_20_20, specific_gravity(deep.sp, 20.0, 1000.0));
+    assert_ne!(deep.sg_20_20, surface.sg_20_20);
+    assert_ne!(deep.sg_25_25, surface.sg_25_25);
+}
+
+#[test]
+fn teos10_properties_match_standalone_functions() {
+    use salinity_rs::adapters::teos10::{
+        alpha, beta, ct_freezing, ct_from_t, sa_from_sp, sigma0, sound_speed, specific_heat,
+    };
+
+    let ass = Assumptions {
+        temp: 12.0,
+        pressure_dbar: 500.0,
+        ..Default::default()
+    };
+    let props = salinity_rs::teos10_properties(35.0, &ass);
+
+    let sa = sa_from_sp(35.0);
+    let ct = ct_from_t(sa, 12.0, 500.0);
+    assert_eq!(props.sa, sa);
+    assert_eq!(props.ct, ct);
+    assert_eq!(props.density_kg_m3, rho_from_sp(35.0, &ass));
+    assert_eq!(props.sound_speed_m_s, sound_speed(sa, ct, 500.0));
+    assert_eq!(props.alpha, alpha(sa, ct, 500.0));
+    assert_eq!(props.beta, beta(sa, ct, 500.0));
+    assert_eq!(props.cp, specific_heat(sa, 12.0, 500.0));
+    assert_eq!(props.sigma0, sigma0(sa, ct));
+    assert_eq!(props.ct_freezing, ct_freezing(sa, 500.0, 1.0));
+
+    // Plausible oceanographic magnitudes.
+    approx_in_range(props.sound_speed_m_s, 1_490.0, 1_510.0);
+    approx_in_range(props.alpha, 1.5e-4, 2.2e-4);
+    approx_in_range(props.beta, 7.0e-4, 7.8e-4);
+    approx_in_range(props.cp, 3_960.0, 4_000.0);
+    approx_in_range(props.sigma0, 26.0, 27.5);
+}
+
+#[test]
+fn pt_difference_grows_with_pressure() {
+    let at = |pressure_dbar: f64| {
+        let ass = Assumptions {
+            temp: 2.0,
+            pressure_dbar,
+            ..Default::default()
+        };
+        salinity_rs::teos10_properties(34.7, &ass).pt_difference
+    };
+    approx_eq(at(0.0), 0.0, 1e-10);
+    // Adiabatic warming in the abyss is a few tenths of a kelvin.
+    approx_in_range(at(4000.0), 0.2, 0.6);
+}
+
+#[test]
+fn dilution_factor_scales_the_measured_panel() {
+    let ass = Assumptions::default();
+    // A 1:10 aliquot of standard seawater, as an ICP lab would report it.
+    let reported = Inputs {
+        si_umol_kg: Some(2.0),
+        ..salinity_rs::mix(
+            &salinity_rs::standard_seawater(35.0, &ass),
+            &Inputs {
+                cl: Some(0.0),
+                f: Some(0.0),
+                alk_dkh: Some(0.0),
+                ..Default::default()
+            },
+            0.1,
+        )
+    };
+    let times_ten = |v: f64| v * 10.0;
+    let manual = Inputs {
+        na: times_ten(reported.na),
+        ca: times_ten(reported.ca),
+        mg: times_ten(reported.mg),
+        k: times_ten(reported.k),
+        sr: times_ten(reported.sr),
+        br: times_ten(reported.br),
+        cl: reported.cl.map(times_ten),
+        f: reported.f.map(times_ten),
+        s: times_ten(reported.s),
+        b: times_ten(reported.b),
+        alk_dkh: reported.alk_dkh.map(times_ten),
+        si_umol_kg: reported.si_umol_kg.map(times_ten),
+        ..reported.clone()
+    };
+    let declared = Inputs {
+        dilution_factor: Some(10.0),
+        ..reported.clone()
+    };
+
+    let expected = salinity_rs::compute_summary(&manual, &ass);
+    let got = salinity_rs::compute_summary(&declared, &ass);
+    assert_eq!(got.sp, expected.sp);
+    assert_eq!(got.sa, expected.sa);
+    assert_eq!(got.density_kg_per_m3, expected.density_kg_per_m3);
+    approx_eq(got.sp, 35.0, 0.05);
+    // Forgetting the factor is an order-of-magnitude error.
+    assert!(salinity_rs::compute_summary(&reported, &ass).sp < 5.0);
+}
+
+#[test]
+fn reconstruct_panel_from_sodium_anchor() {
+    use salinity_rs::{Species, reconstruct_panel, reconstruct_panel_checked};
+
+    let ass = Assumptions::default();
+    let standard = salinity_rs::standard_seawater(35.0, &ass);
+    let panel = reconstruct_panel(35.0, (Species::Na, standard.na));
+    approx_eq(panel.na, standard.na, 1e-9);
+    approx_eq(panel.mg, standard.mg, 1e-9);
+    approx_eq(salinity_rs::compute_summary(&panel, &ass).sp, 35.0, 0.01);
+
+    // Sodium at 90 % of its standard value implies ~10 % fresher water.
+    let fresher = reconstruct_panel_checked(35.0, (Species::Na, 0.9 * standard.na), &ass);
+    approx_eq(fresher.inputs.na, 0.9 * standard.na, 1e-9);
+    approx_eq(fresher.panel_sp, 31.5, 0.1);
+    assert!(!fresher.consistent);
+    assert!(reconstruct_panel_checked(35.0, (Species::So4, 2_780.0), &ass).consistent);
+}
+
+#[test]
+fn record_history_traces_the_solver_to_convergence() {
+    use salinity_rs::calc_all;
+
+    let ass = Assumptions {
+        record_history: true,
+        ..Default::default()
+    };
+    let inputs = salinity_rs::standard_seawater(30.0, &ass);
+    let max_iter = 30;
+    let detailed = calc_all(&inputs, &ass, max_iter, 1e-10);
+    let history = detailed.iteration_history.expect("history recorded");
+
+    assert!(!history.is_empty() && history.len() <= max_iter);
+    let last = *history.last().unwrap();
+    approx_eq(last, detailed.sp, 1e-4);
+    // Starting from SP 35, every update moves closer to the converged value.
+    let errors: Vec<f64> = history.iter().map(|sp| (sp - last).abs()).collect();
+    assert!(errors.windows(2).all(|w| w[1] <= w[0]), "{history:?}");
+    approx_in_range(history[0], 29.0, 31.0);
+
+    // Off by default.
+    let plain = calc_all(&inputs, &Assumptions::default(), max_iter, 1e-10);
+    assert!(plain.iteration_history.is_none());
+}
+
+#[test]
+fn detailed_result_round_trips_through_json() {
+    use salinity_rs::{DetailedResult, calc_salinity_sp_teos10};
+
+    let ass = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+    let inputs = salinity_rs::standard_seawater(33.0, &ass);
+    let CalcResult::Detailed(detailed) = calc_salinity_sp_teos10(&inputs, &ass, 30, 1e-8) else {
+        panic!("expected detailed output");
+    };
+
+    let json = serde_json::to_string(&detailed).unwrap();
+    let back: DetailedResult = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.sp, detailed.sp);
+    assert_eq!(back.sr, detailed.sr);
+    assert_eq!(back.rho_kg_m3, detailed.rho_kg_m3);
+    assert_eq!(back.so4_mg_l, detailed.so4_mg_l);
+    assert_eq!(back.so4_source, detailed.so4_source);
+    let (a, b) = (&back.components, &detailed.components);
+    assert_eq!(a.mg_l, b.mg_l);
+    assert_eq!(a.mgkg, b.mgkg);
+    assert_eq!(a.mg_l_sp35, b.mg_l_sp35);
+    assert_eq!(a.mgkg_sp35, b.mgkg_sp35);
+    assert_eq!(a.mgkg_sa_ref, b.mgkg_sa_ref);
+    assert_eq!(a.estimated, b.estimated);
+    assert_eq!(a.norm_factor, b.norm_factor);
+    assert_eq!(a.norm_factor_sa, b.norm_factor_sa);
+
+    let simple: CalcResult = serde_json::from_str(r#"{"simple":35.0}"#).unwrap();
+    assert!(matches!(simple, CalcResult::Simple(sp) if sp == 35.0));
+    let unknown = json.replace("\"Na+\"", "\"Xx+\"");
+    assert!(serde_json::from_str::<DetailedResult>(&unknown).is_err());
+}
+
+#[test]
+fn compute_summaries_reports_progress_per_sample() {
+    let ass = Assumptions::default();
+    let samples: Vec<_> = [30.0, 33.0, 35.0, 38.0]
+        .iter()
+        .map(|&sp| (salinity_rs::standard_seawater(sp, &ass), ass.clone()))
+        .collect();
+
+    let mut calls = Vec::new();
+    let mut record = |done: usize, total: usize| calls.push((done, total));
+    let summaries = salinity_rs::compute_summaries(&samples, Some(&mut record));
+    assert_eq!(calls, [(1, 4), (2, 4), (3, 4), (4, 4)]);
+
+    let silent = salinity_rs::compute_summaries(&samples, None);
+    for (((inputs, ass), a), b) in samples.iter().zip(&summaries).zip(&silent) {
+        let expected = salinity_rs::compute_summary(inputs, ass);
+        assert_eq!(a.sp, expected.sp);
+        assert_eq!(b.sp, expected.sp);
+    }
+}
+
+#[test]
+fn sp_anomaly_against_reference_profile() {
+    use salinity_rs::sp_anomaly;
+
+    let profile = [(0.0, 35.0), (100.0, 35.4), (500.0, 34.6)];
+    // 50 dbar interpolates to 35.2, 300 dbar to 35.0.
+    approx_eq(sp_anomaly(35.2, &profile, 50.0), 0.0, 1e-12);
+    approx_eq(sp_anomaly(35.0, &profile, 300.0), 0.0, 1e-12);
+    approx_eq(sp_anomaly(35.4, &profile, 100.0), 0.0, 1e-12);
+    let above = sp_anomaly(35.3, &profile, 50.0);
+    assert!(above > 0.0);
+    approx_eq(above, 0.1, 1e-12);
+    assert!(sp_anomaly(35.0, &profile, 800.0).is_nan());
+    assert!(sp_anomaly(35.0, &[], 0.0).is_nan());
+}
+
+#[test]
+fn per_sample_conditions_reach_tds_and_mass_closure() {
+    use salinity_rs::{ConcentrationBasis, mass_closure_pct, tds_mg_l};
+
+    // On a per-kilogram basis the mg/kg → mg/L conversion depends on the
+    // in-situ density, so the sample temperature must be honoured.
+    let ass = Assumptions {
+        input_basis: ConcentrationBasis::PerKilogram,
+        ..Default::default()
+    };
+    let panel = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+    let tagged = Inputs {
+        t_c: Some(2.0),
+        ..panel.clone()
+    };
+    let cold = Assumptions {
+        temp: 2.0,
+        ..ass.clone()
+    };
+    assert_eq!(tds_mg_l(&tagged, &ass), tds_mg_l(&panel, &cold));
+    assert!(tds_mg_l(&tagged, &ass) > tds_mg_l(&panel, &ass));
+    assert_eq!(
+        mass_closure_pct(&tagged, &ass),
+        mass_closure_pct(&panel, &cold)
+    );
+}
+
+#[test]
+fn per_sample_conditions_override_assumptions() {
+    let ass = Assumptions::default();
+    let panel = salinity_rs::standard_seawater(35.0, &ass);
+    let warm = Assumptions {
+        temp: 28.0,
+        pressure_dbar: 50.0,
+        ..Default::default()
+    };
+
+    let tagged = Inputs {
+        t_c: Some(28.0),
+        p_dbar: Some(50.0),
+        ..panel.clone()
+    };
+    let by_sample = salinity_rs::compute_summary(&tagged, &ass);
+    let by_assumptions = salinity_rs::compute_summary(&panel, &warm);
+    assert_eq!(
+        by_sample.density_kg_per_m3,
+        by_assumptions.density_kg_per_m3
+    );
+    assert_eq!(by_sample.sp, by_assumptions.sp);
+    assert!(
+        by_sample.density_kg_per_m3 < salinity_rs::compute_summary(&panel, &ass).density_kg_per_m3
+    );
+
+    // Only the fields present override; the sample temperature wins even
+    // over explicit assumptions.
+    let t_only = Inputs {
+        t_c: Some(28.0),
+        ..panel
+    };
+    let cold_deep = Assumptions {
+        temp: 2.0,
+        pressure_dbar: 50.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        salinity_rs::compute_full(&t_only, &cold_deep)
+            .summary
+            .density_kg_per_m3,
+        by_assumptions.density_kg_per_m3
+    );
+}
+
+#[test]
+fn d2o_fraction_raises_density() {
+    let plain = Assumptions::default();
+    let none = rho_from_sp(35.0, &plain);
+    let zero = rho_from_sp(
+        35.0,
+        &Assumptions {
+            d2o_fraction: Some(0.0),
+            ..Default::default()
+        },
+    );
+    assert_eq!(zero, none);
+
+    let labelled = Assumptions {
+        d2o_fraction: Some(0.1),
+        ..Default::default()
+    };
+    let heavy = rho_from_sp(35.0, &labelled);
+    assert!(heavy > none);
+    approx_eq(heavy / none, 1.0 + 0.1 * 0.1073, 1e-12);
+}
+
+#[test]
+fn species_order_leads_every_component_table() {
+    let default = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+    let reordered = Assumptions {
+        species_order: Some(vec!["Cl-".into(), "Na+".into(), "Xx".into()]),
+        ..default.clone()
+    };
+    let inputs = salinity_rs::standard_seawater(35.0, &default);
+    let run = |ass: &Assumptions| match calc_salinity_sp_teos10(&inputs, ass, 30, 1e-8) {
+        CalcResult::Detailed(d) => d.components,
+        CalcResult::Simple(_) => panic!("expected detailed result"),
+    };
+    let (a, b) = (run(&default), run(&reordered));
+
+    let names = |t: &[(&str, f64)]| t.iter().map(|(k, _)| *k).collect::<Vec<_>>();
+    for table in [&b.mg_l, &b.mgkg, &b.mg_l_sp35, &b.mgkg_sp35] {
+        assert_eq!(names(table)[..3], ["Cl-", "Na+", "Ca2+"]);
+        assert_eq!(table.len(), a.mg_l.len());
+    }
+    assert_eq!(b.estimated[0].0, "Cl-");
+    assert_eq!(names(&a.mg_l)[0], "Na+");
+    let cl = |t: &[(&str, f64)]| t.iter().find(|(k, _)| *k == "Cl-").unwrap().1;
+    assert_eq!(cl(&a.mg_l), cl(&b.mg_l));
+}
+
+#[test]
+fn calc_all_matches_simple_sp() {
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(34.2, &ass);
+    let CalcResult::Simple(simple) =
+        salinity_rs::calc_salinity_sp_iterative(&inputs, &ass, 30, 1e-8)
+    else {
+        panic!("expected simple result");
+    };
+    let all = salinity_rs::calc_all(&inputs, &ass, 30, 1e-8);
+    assert_eq!(all.sp, simple);
+    assert!(!all.components.mg_l.is_empty());
+}
+
+#[test]
+fn density_anomaly_against_own_density_is_zero() {
+    use salinity_rs::density_anomaly;
+
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    let own = salinity_rs::compute_summary(&inputs, &ass).density_kg_per_m3;
+    assert_eq!(density_anomaly(&inputs, &ass, own), 0.0);
+    approx_eq(density_anomaly(&inputs, &ass, own - 0.5), 0.5, 1e-9);
+}
+
+#[test]
+fn measured_density_equal_to_computed_reproduces_result() {
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    let computed = salinity_rs::calc_all(&inputs, &ass, 50, 1e-12);
+
+    let measured = Assumptions {
+        measured_density: Some(computed.rho_kg_m3),
+        ..Default::default()
+    };
+    let fixed = salinity_rs::calc_all(&inputs, &measured, 50, 1e-12);
+    approx_eq(fixed.sr, computed.sr, 1e-9);
+    assert_eq!(fixed.sp, computed.sp);
+    assert_eq!(fixed.rho_kg_m3, computed.rho_kg_m3);
+
+    // A denser sample holds fewer grams per kilogram for the same mg/L.
+    let denser = Assumptions {
+        measured_density: Some(computed.rho_kg_m3 + 5.0),
+        ..Default::default()
+    };
+    assert!(salinity_rs::calc_all(&inputs, &denser, 50, 1e-12).sr < computed.sr);
+}
+
+#[test]
+fn recipe_density_of_35_g_per_litre_is_seawater_like() {
+    let ass = Assumptions {
+        temp: 25.0,
+        ..Default::default()
+    };
+    let rho = salinity_rs::recipe_density(35.0, 1.0, &ass);
+    // 35 g in ~0.997 kg of water is SA ≈ 33.9 g/kg.
+    approx_in_range(rho, 1021.0, 1024.5);
+    assert!(salinity_rs::recipe_density(40.0, 1.0, &ass) > rho);
+    assert!(salinity_rs::recipe_density(0.0, 0.0, &ass).is_nan());
+}
+
+#[test]
+fn delta_sa_shifts_reported_sa() {
+    let ass = Assumptions::default();
+    let inputs = salinity_rs::standard_seawater(35.0, &ass);
+    let plain = salinity_rs::compute_summary(&inputs, &ass);
+    let corrected = salinity_rs::compute_summary(
+        &inputs,
+        &Assumptions {
+            delta_sa: Some(0.02),
+            ..Default::default()
+        },
+    );
+    approx_eq(corrected.sa - plain.sa, 0.02, 1e-12);
+    approx_eq(corrected.sa_anomaly_g_kg, 0.02, 1e-15);
+    assert_eq!(corrected.sp, plain.sp);
+    assert_eq!(corrected.density_kg_per_m3, plain.density_kg_per_m3);
+}
+
+#[test]
+fn all_zero_panel_gives_finite_pure_water_result() {
+    use salinity_rs::Warning;
+
+    let ass = Assumptions {
+        return_components: true,
+        ..Default::default()
+    };
+    let full = salinity_rs::compute_full(&Inputs::default(), &ass);
+    assert!(full.warnings.contains(&Warning::ZeroPanel));
+    assert!(!full.warnings.contains(&Warning::NotSeawater));
+    assert_eq!(full.summary.sp, 0.0);
+    assert_eq!(full.summary.normalized_sp, 0.0);
+    approx_eq(full.summary.density_kg_per_m3, rho_from_sp(0.0, &ass), 1e-9);
+    let components = full.components.expect("components requested");
+    assert_eq!(components.norm_factor, 0.0);
+    assert_eq!(components.norm_factor_sa, 0.0);
+    for table in [
+        &components.mg_l,
+        &components.mg_l_sp35,
+        &components.mgkg_sp35,
+        &components.mgkg_sa_ref,
+    ] {
+        assert!(table.iter().all(|(_, v)| *v == 0.0), "{table:?}");
+    }
+}
+
+#[test]
+fn rn_compat_changes_reported_reference_sum() {
+    let plain = Assumptions::default();
+    let rn = Assumptions {
+        rn_compat: true,
+        ..Default::default()
+    };
+    // rn_compat lowers the reference alkalinity from 8.0 to 6.2 dKH.
+    let (sum_plain, sum_rn) = (
+        salinity_rs::effective_ref_sum(&plain),
+        salinity_rs::effective_ref_sum(&rn),
+    );
+    assert!(sum_rn < sum_plain, "{sum_rn} vs {sum_plain}");
+    approx_in_range(sum_plain, 34.5, 36.5);
+
+    let inputs = salinity_rs::standard_seawater(35.0, &plain);
+    let detailed = salinity_rs::calc_all(&inputs, &rn.clone().normalized(), 50, 1e-10);
+    assert_eq!(detailed.ref_sum_gkg, sum_rn);
+}
+
+#[test]
+fn per_kilogram_panel_converges_to_the_per_litre_sp() {
+    use salinity_rs::ConcentrationBasis;
+
+    let ass = Assumptions {
+        temp: 25.0,
+        ..Default::default()
+    };
+    let per_l = salinity_rs::standard_seawater(30.0, &ass);
+    let kg_per_l = rho_from_sp(30.0, &ass) / 1000.0;
+    let per_kg = Inputs {
+        na: per_l.na / kg_per_l,
+        ca: per_l.ca / kg_per_l,
+        mg: per_l.mg / kg_per_l,
+        k: per_l.k / kg_per_l,
+        sr: per_l.sr / kg_per_l,
+        br: per_l.br / kg_per_l,
+        cl: per_l.cl.map(|v| v / kg_per_l),
+        f: per_l.f.map(|v| v / kg_per_l),
+        s: per_l.s / kg_per_l,
+        b: per_l.b / kg_per_l,
+        ..per_l.clone()
+    };
+    let per_kg_ass = Assumptions {
+        input_basis: ConcentrationBasis::PerKilogram,
+        ..ass.clone()
+    };
+
+    let sp_l = salinity_rs::compute_summary(&per_l, &ass).sp;
+    let sp_kg = salinity_rs::compute_summary(&per_kg, &per_kg_ass).sp;
+    approx_eq(sp_kg, sp_l, 2e-4);
+    approx_eq(sp_kg, 30.0, 1e-2);
+    // Read per litre, the same numbers are ~2 % too dilute.
+    assert!(salinity_rs::compute_summary(&per_kg, &ass).sp < sp_l - 0.3);
+}
+
+#[test]
+fn composition_sa_tracks_pss_sa_only_for_standard_composition() {
+    // SP as a conductivity meter would report it.
+    let ass = Assumptions {
+        fixed_sp: Some(35.0),
+        report_composition_sa: true,
+        ..Default::default()
+    };
+    let standard = salinity_rs::standard_seawater(35.0, &Assumptions::default());
+    let summary = salinity_rs::compute_summary(&standard, &ass);
+    let sa_comp = summary.sa_composition_g_kg.expect("requested");
+    approx_eq(sa_comp, summary.sa, 0.3);
+    assert!(
+        salinity_rs::compute_summary(&standard, &Assumptions::default())
+            .sa_composition_g_kg
+            .is_none()
+    );
+
+    let ca_enriched = Inputs {
+        ca: standard.ca + 2000.0,
+        ..standard
+    };
+    let summary = salinity_rs::compute_summary(&ca_enriched, &ass);
+    let sa_comp = summary.sa_composition_g_kg.unwrap();
+    assert!(sa_comp - summary.sa > 1.5, "{sa_comp} vs {}", summary.sa);
 }
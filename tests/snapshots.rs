@@ -0,0 +1,91 @@
+//! Regression snapshots: each `tests/snapshots/<name>.input.json` holds
+//! `{ "inputs": ..., "assumptions": ... }` and `<name>.expected.json` the
+//! serialized `compute_full` result. Numbers are compared with a relative
+//! tolerance; everything else must match exactly.
+//!
+//! Regenerate the expected files with `UPDATE_SNAPSHOTS=1 cargo test --test snapshots`.
+
+use salinity_rs::{Assumptions, Inputs, compute_full};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+const REL_TOL: f64 = 1e-9;
+
+#[derive(serde::Deserialize)]
+struct Fixture {
+    inputs: Inputs,
+    #[serde(default)]
+    assumptions: Assumptions,
+}
+
+fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+}
+
+fn compare(path: &str, actual: &Value, expected: &Value, diffs: &mut Vec<String>) {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(e)) => {
+            let (a, e) = (a.as_f64().unwrap(), e.as_f64().unwrap());
+            if (a - e).abs() > REL_TOL * e.abs().max(1.0) {
+                diffs.push(format!("{path}: {a} != {e}"));
+            }
+        }
+        (Value::Array(a), Value::Array(e)) if a.len() == e.len() => {
+            for (i, (a, e)) in a.iter().zip(e).enumerate() {
+                compare(&format!("{path}[{i}]"), a, e, diffs);
+            }
+        }
+        (Value::Object(a), Value::Object(e))
+            if a.len() == e.len() && a.keys().all(|k| e.contains_key(k)) =>
+        {
+            for (k, a) in a {
+                compare(&format!("{path}.{k}"), a, &e[k], diffs);
+            }
+        }
+        _ if actual == expected => {}
+        _ => diffs.push(format!("{path}: {actual} != {expected}")),
+    }
+}
+
+#[test]
+fn snapshots_match_expected_output() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut inputs: Vec<PathBuf> = std::fs::read_dir(snapshot_dir())
+        .expect("tests/snapshots exists")
+        .map(|entry| entry.unwrap().path())
+        .filter(|p| p.to_string_lossy().ends_with(".input.json"))
+        .collect();
+    inputs.sort();
+    assert!(inputs.len() >= 3, "expected at least three fixtures");
+
+    let mut failures = Vec::new();
+    for input in &inputs {
+        let name = input.to_string_lossy().replace(".input.json", "");
+        let fixture: Fixture =
+            serde_json::from_str(&std::fs::read_to_string(input).unwrap()).unwrap();
+        let actual =
+            serde_json::to_value(compute_full(&fixture.inputs, &fixture.assumptions)).unwrap();
+
+        let expected_path = PathBuf::from(format!("{name}.expected.json"));
+        if update {
+            let text = serde_json::to_string_pretty(&actual).unwrap() + "\n";
+            std::fs::write(&expected_path, text).unwrap();
+            continue;
+        }
+        let expected: Value = serde_json::from_str(
+            &std::fs::read_to_string(&expected_path)
+                .unwrap_or_else(|e| panic!("{}: {e}", expected_path.display())),
+        )
+        .unwrap();
+        let mut diffs = Vec::new();
+        compare("$", &actual, &expected, &mut diffs);
+        if !diffs.is_empty() {
+            failures.push(format!("{}:\n  {}", name, diffs.join("\n  ")));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "snapshot drift (rerun with UPDATE_SNAPSHOTS=1 if intended):\n{}",
+        failures.join("\n")
+    );
+}